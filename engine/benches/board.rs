@@ -0,0 +1,152 @@
+//! Compares win detection on bitboards with the scan of a `HashMap` board it
+//! replaced, on boards much larger than the GUI's defaults. Run with
+//! `cargo bench -p four-in-a-row-engine`.
+//!
+//! Every board replays the same random games three ways: scanning a `HashMap`
+//! of the pieces after each move as the game used to, playing each move with
+//! its incremental check, and undoing each move, which rescans the bitboards.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use four_in_a_row_engine::{Game, GameState, GameTrait, Placement, Player, PlayerType, Point};
+
+const GAMES: usize = 10;
+
+const DIRECTIONS: [Point; 4] = [
+    Point { x: 1, y: 0 },
+    Point { x: -1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: 1, y: 1 },
+];
+
+fn main() {
+    for (width, height, goal, players) in [
+        (7, 6, 4, 2),
+        (19, 19, 5, 2),
+        (50, 50, 6, 4),
+        (100, 100, 8, 8),
+    ] {
+        let games = random_games(width, height, goal, players);
+        let moves: usize = games.iter().map(Vec::len).sum();
+        let per_move = |elapsed: Duration| elapsed / moves.max(1) as u32;
+
+        let hashmap = time_hashmap_scans(&games, goal, players);
+        let (incremental, bitboard) = time_bitboards(&games, width, height, goal, players);
+        println!("{width}x{height}, goal {goal}, {players} players, {moves} moves:");
+        println!("  HashMap scan      {:>12?} per move", per_move(hashmap));
+        println!("  bitboard scan     {:>12?} per move", per_move(bitboard));
+        println!(
+            "  incremental check {:>12?} per move",
+            per_move(incremental)
+        );
+    }
+}
+
+/// The moves of [`GAMES`] games of random moves, each played until someone
+/// wins or the board is full
+fn random_games(width: isize, height: isize, goal: isize, players: usize) -> Vec<Vec<Point>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..GAMES)
+        .map(|_| {
+            let mut cells: Vec<_> = (0..height)
+                .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+                .collect();
+            cells.shuffle(&mut rng);
+            let mut game = new_game(width, height, goal, players);
+            cells
+                .into_iter()
+                .take_while(|&point| {
+                    let playing = matches!(game.get_gamestate(), GameState::PlayerMove(_));
+                    if playing {
+                        game.play_move(point).unwrap();
+                    }
+                    playing
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn new_game(width: isize, height: isize, goal: isize, players: usize) -> Game {
+    Game::new(
+        width,
+        height,
+        goal,
+        vec![PlayerType::User; players],
+        Placement::Free,
+    )
+    .unwrap()
+}
+
+/// Times playing every game with the pieces in a `HashMap`, scanning every
+/// piece for a line after each move
+fn time_hashmap_scans(games: &[Vec<Point>], goal: isize, players: usize) -> Duration {
+    let start = Instant::now();
+    for moves in games {
+        let mut board = HashMap::new();
+        let mut history = Vec::new();
+        for (turn, &point) in moves.iter().enumerate() {
+            let player = turn % players;
+            board.insert(point, player);
+            history.push((point, player));
+            std::hint::black_box(hashmap_winner(&board, &history, goal));
+        }
+    }
+    start.elapsed()
+}
+
+/// The player who completed a line, found by looking for a run from every
+/// piece in every direction
+fn hashmap_winner(
+    board: &HashMap<Point, Player>,
+    history: &[(Point, Player)],
+    goal: isize,
+) -> Option<Player> {
+    history.iter().rev().find_map(|&(point, player)| {
+        DIRECTIONS
+            .iter()
+            .any(|&direction| {
+                // Only count each run once, from its first cell
+                if board.get(&(point + direction * -1)) == Some(&player) {
+                    return false;
+                }
+                let length = (1..)
+                    .take_while(|&i| board.get(&(point + direction * i)) == Some(&player))
+                    .count() as isize
+                    + 1;
+                length >= goal
+            })
+            .then_some(player)
+    })
+}
+
+/// Times playing every game, which checks each move incrementally, and then
+/// undoing every move, which rescans the bitboards
+fn time_bitboards(
+    games: &[Vec<Point>],
+    width: isize,
+    height: isize,
+    goal: isize,
+    players: usize,
+) -> (Duration, Duration) {
+    let mut incremental = Duration::ZERO;
+    let mut scans = Duration::ZERO;
+    for moves in games {
+        let mut game = new_game(width, height, goal, players);
+        let start = Instant::now();
+        for &point in moves {
+            game.play_move(point).unwrap();
+        }
+        incremental += start.elapsed();
+
+        let start = Instant::now();
+        while game.undo() {}
+        scans += start.elapsed();
+    }
+    (incremental, scans)
+}
@@ -0,0 +1,42 @@
+//! Plays over stdin and stdout using the line protocol described on
+//! [`Request`], so other programs can drive the game and its bots.
+//!
+//! Takes the path of a weights file to play with as its only argument, falling
+//! back to the built-in weights if there is none.
+
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    sync::Arc,
+};
+
+use four_in_a_row_engine::{Request, Response, Session, WeightTable};
+
+fn main() -> io::Result<()> {
+    let weight_table = match env::args().nth(1) {
+        Some(path) => WeightTable::load(path).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }),
+        None => WeightTable::default(),
+    };
+    let mut session = Session::new(Arc::new(weight_table));
+
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (response, quit) = match line.parse::<Request>() {
+            Ok(request) => (session.handle(request), request == Request::Quit),
+            Err(error) => (Response::Error(error.to_string()), false),
+        };
+        writeln!(stdout, "{response}")?;
+        stdout.flush()?;
+        if quit {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::ops::BitAnd;
+
+use super::Point;
+
+const WORD: usize = u64::BITS as usize;
+
+/// A set of cells on a board of any size, stored one bit per cell.
+///
+/// Rows are laid out with a stride of `width + 1`, leaving an always-empty
+/// padding column so that shifting the set never wraps a run from the end of
+/// one row onto the start of the next.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    words: Vec<u64>,
+    width: isize,
+    height: isize,
+}
+
+impl BitBoard {
+    /// An empty set for a board of the given size, which must already have
+    /// been checked by [`Game::validate`](crate::Game::validate)
+    pub fn new(width: isize, height: isize) -> Self {
+        let bits = width
+            .checked_add(1)
+            .and_then(|stride| stride.checked_mul(height))
+            .and_then(|bits| usize::try_from(bits).ok())
+            .expect("the board size fits in memory");
+        Self {
+            words: vec![0; bits.div_ceil(WORD)],
+            width,
+            height,
+        }
+    }
+
+    fn stride(&self) -> isize {
+        self.width + 1
+    }
+
+    fn index(&self, point: &Point) -> Option<usize> {
+        if point.x < 0 || point.x >= self.width || point.y < 0 || point.y >= self.height {
+            None
+        } else {
+            Some((point.x + point.y * self.stride()) as usize)
+        }
+    }
+
+    fn point(&self, index: usize) -> Point {
+        let index = index as isize;
+        Point::new(index % self.stride(), index / self.stride())
+    }
+
+    /// The bit offset between a cell and its neighbour in `direction`
+    pub fn shift(&self, direction: &Point) -> isize {
+        direction.x + direction.y * self.stride()
+    }
+
+    pub fn get(&self, point: &Point) -> bool {
+        self.index(point)
+            .map(|i| self.words[i / WORD] & (1 << (i % WORD)) != 0)
+            .unwrap_or(false)
+    }
+
+    pub fn set(&mut self, point: &Point) {
+        if let Some(i) = self.index(point) {
+            self.words[i / WORD] |= 1 << (i % WORD);
+        }
+    }
+
+    pub fn unset(&mut self, point: &Point) {
+        if let Some(i) = self.index(point) {
+            self.words[i / WORD] &= !(1 << (i % WORD));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| {
+                (0..WORD)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| i * WORD + bit)
+            })
+            .map(|index| self.point(index))
+    }
+
+    /// Moves every cell `bits` positions towards the start of the board
+    fn shift_down(&self, bits: usize) -> Self {
+        let words = bits / WORD;
+        let offset = bits % WORD;
+        let len = self.words.len();
+        Self {
+            words: (0..len)
+                .map(|i| {
+                    let low = self.words.get(i + words).copied().unwrap_or(0);
+                    let high = self.words.get(i + words + 1).copied().unwrap_or(0);
+                    if offset == 0 {
+                        low
+                    } else {
+                        (low >> offset) | (high << (WORD - offset))
+                    }
+                })
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the cells that start a run of at least `length` cells heading in
+    /// `direction`, by repeatedly and-ing the board with shifted copies of itself.
+    pub fn runs(&self, direction: &Point, length: isize) -> Self {
+        let shift = self.shift(direction);
+        let mut runs = self.clone();
+        let mut found = 1;
+        while found < length && !runs.is_empty() {
+            let step = isize::min(found, length - found);
+            runs = &runs & &runs.shift_down((step * shift) as usize);
+            found += step;
+        }
+        runs
+    }
+}
+
+impl BitAnd for &BitBoard {
+    type Output = BitBoard;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        BitBoard {
+            words: self
+                .words
+                .iter()
+                .zip(rhs.words.iter())
+                .map(|(a, b)| a & b)
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
@@ -0,0 +1,863 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{
+    rngs::StdRng,
+    seq::{index, SliceRandom},
+    Rng, SeedableRng,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    parse_name, ComputerWeights, Game, GameState, GameTrait, ParseNameError, Placement, Player,
+    Point, DIRECTIONS,
+};
+
+/// A computer player: how strong it is, what it prioritises and how it searches
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct Bot(pub Difficulty, pub Strategy, pub Engine);
+
+impl Bot {
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.0
+    }
+
+    pub fn get_strategy(&self) -> Strategy {
+        self.1
+    }
+
+    pub fn get_engine(&self) -> Engine {
+        self.2
+    }
+}
+
+/// Written as `difficulty:strategy:engine`, such as `Hard:Offensive:Alpha-Beta`
+impl Display for Bot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.0, self.1, self.2)
+    }
+}
+
+/// Parses the form [`Bot`] is displayed in. The strategy and engine can be left
+/// off, in which case they take their defaults.
+impl FromStr for Bot {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let difficulty = parts.next().unwrap_or_default().parse()?;
+        let strategy = parts.next().map(str::parse).transpose()?;
+        let engine = parts.next().map(str::parse).transpose()?;
+        match parts.next() {
+            Some(extra) => Err(ParseNameError {
+                kind: "bot part",
+                name: extra.to_string(),
+                expected: vec![String::from("difficulty:strategy:engine")],
+            }),
+            None => Ok(Bot(
+                difficulty,
+                strategy.unwrap_or_default(),
+                engine.unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+/// How strong a bot plays, mostly by how far ahead it searches
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Insane];
+
+    /// How many moves ahead the search engines look
+    pub fn get_search_depth(&self) -> usize {
+        match self {
+            Self::Easy => 1,
+            Self::Normal => 2,
+            Self::Hard => 3,
+            Self::Insane => 4,
+        }
+    }
+
+    /// How long the Monte Carlo engine searches for
+    pub fn get_budget(&self) -> Budget {
+        match self {
+            Self::Easy => Budget::Iterations(250),
+            Self::Normal => Budget::Iterations(1000),
+            Self::Hard => Budget::Iterations(4000),
+            Self::Insane => Budget::Iterations(8000),
+        }
+    }
+
+    /// How the Monte Carlo engine picks moves when playing out a game
+    pub fn get_playout(&self) -> Playout {
+        match self {
+            Self::Easy | Self::Normal => Playout::Random,
+            Self::Hard | Self::Insane => Playout::Guided,
+        }
+    }
+}
+
+/// How much work the Monte Carlo engine does before picking a move
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Budget {
+    /// Stop after this many playouts, or after [`MONTE_CARLO_TIME_LIMIT`]
+    Iterations(usize),
+    /// Keep playing out games until this much time has passed
+    Time(Duration),
+}
+
+/// How the Monte Carlo engine plays out games to their end
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Playout {
+    /// Every move in a playout is picked uniformly at random
+    Random,
+    /// Each move in a playout is the best of a few random picks, as scored by
+    /// the heuristic
+    Guided,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Insane => "Insane",
+        })
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_name("difficulty", &Self::ALL, s)
+    }
+}
+
+/// Whether a bot favours building its own lines or blocking its opponents'
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Strategy {
+    #[default]
+    Neutral,
+    Offensive,
+    Defensive,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Self::Neutral, Self::Offensive, Self::Defensive];
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Neutral => "Neutral",
+            Self::Offensive => "Offensive",
+            Self::Defensive => "Defensive",
+        })
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_name("strategy", &Self::ALL, s)
+    }
+}
+
+/// The algorithm a bot uses to choose its move
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Engine {
+    /// Plays the best looking move on the current board
+    #[default]
+    Heuristic,
+    /// Looks ahead with a negamax search, scoring positions with the heuristic
+    AlphaBeta,
+    /// Looks ahead assuming every player plays for their own best score
+    MaxN,
+    /// Looks ahead assuming every other player plays against this one
+    Paranoid,
+    /// Plays out random games from each move and picks the one that wins most
+    MonteCarlo,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 5] = [
+        Self::Heuristic,
+        Self::AlphaBeta,
+        Self::MaxN,
+        Self::Paranoid,
+        Self::MonteCarlo,
+    ];
+
+    /// Whether the engine can play a game of `player_count` players. Alpha-beta
+    /// search takes every reply to be the one opponent's, so it only plays two.
+    pub fn supports(&self, player_count: usize) -> bool {
+        *self != Self::AlphaBeta || player_count == 2
+    }
+}
+
+impl Display for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Heuristic => "Heuristic",
+            Self::AlphaBeta => "Alpha-Beta",
+            Self::MaxN => "Max-N",
+            Self::Paranoid => "Paranoid",
+            Self::MonteCarlo => "Monte Carlo",
+        })
+    }
+}
+
+impl FromStr for Engine {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_name("engine", &Self::ALL, s)
+    }
+}
+
+/// Score given to a won position, well clear of anything the heuristic produces
+const WIN_SCORE: i64 = i64::MAX / 4;
+
+/// How many of the most promising moves the search engines expand at each node
+const SEARCH_BREADTH: usize = 8;
+
+/// Narrower breadth for the multi-player engines, which have to search deeper
+/// to see a full round of replies
+const MULTIPLAYER_SEARCH_BREADTH: usize = 5;
+
+const MAX_MULTIPLAYER_DEPTH: usize = 5;
+
+/// Exploration constant for choosing which branch of the tree to play out next
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// How many random moves a guided playout picks the best from
+const GUIDED_PLAYOUT_SAMPLES: usize = 3;
+
+/// Longest the Monte Carlo engine thinks when given a number of playouts.
+/// Only huge boards come near it, so seeded games on ordinary boards play the
+/// same every time.
+pub const MONTE_CARLO_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+/// Extends a two player search depth for a game with `player_count` players
+fn extend_search_depth(depth: usize, player_count: usize) -> usize {
+    usize::min(depth + player_count - 2, MAX_MULTIPLAYER_DEPTH)
+}
+
+impl Game {
+    /// How many moves ahead `bot` looks when playing as `player`, from its
+    /// profile if it has one and from its difficulty otherwise
+    fn get_search_depth(&self, player: Player, bot: Bot) -> usize {
+        let depth = self
+            .get_player_profile(player)
+            .and_then(|profile| profile.search_depth)
+            .unwrap_or_else(|| bot.get_difficulty().get_search_depth());
+        if self.get_player_count() == 2 {
+            depth
+        } else {
+            extend_search_depth(depth, self.get_player_count())
+        }
+    }
+
+    /// How many of the most promising moves the search engines expand for
+    /// `player`, from its profile if it has one and `default` otherwise
+    fn get_search_breadth(&self, player: Player, default: usize) -> usize {
+        self.get_player_profile(player)
+            .and_then(|profile| profile.search_breadth)
+            .unwrap_or(default)
+    }
+
+    /// The move `bot` would play for the current player, or `None` if there are
+    /// no moves left. The randomness comes from the game's seed and the number of
+    /// moves played, so the same position in the same game always gets the same
+    /// move.
+    pub fn get_computer_move(&self, bot: Bot) -> Option<Point> {
+        let mut rng = StdRng::seed_from_u64(self.get_seed() ^ self.get_history().len() as u64);
+        self.get_computer_move_with_rng(bot, &mut rng)
+    }
+
+    /// Like [`Game::get_computer_move`], drawing any randomness from `rng`
+    pub fn get_computer_move_with_rng<R: Rng + ?Sized>(
+        &self,
+        bot: Bot,
+        rng: &mut R,
+    ) -> Option<Point> {
+        match bot.get_engine() {
+            Engine::Heuristic => self.get_heuristic_move(bot, rng),
+            Engine::AlphaBeta => self.get_alpha_beta_move(bot),
+            Engine::Paranoid => self.get_paranoid_move(bot),
+            Engine::MaxN => self.get_max_n_move(bot),
+            Engine::MonteCarlo => self.get_monte_carlo_move(
+                bot,
+                bot.get_difficulty().get_budget(),
+                bot.get_difficulty().get_playout(),
+                rng,
+            ),
+        }
+    }
+
+    fn get_heuristic_move<R: Rng + ?Sized>(&self, bot: Bot, rng: &mut R) -> Option<Point> {
+        let weights = self.get_weights(self.get_current_player(), bot);
+        let computer = self.get_current_player();
+        let evals: Vec<_> = self
+            .get_valid_moves()
+            .into_iter()
+            .map(|point| (point, self.evaluate_location(point, computer, &weights)))
+            .collect();
+
+        let max_evaluation = *evals.iter().map(|(_, i)| i).max().unwrap_or(&0);
+
+        let max_moves: Vec<_> = evals
+            .into_iter()
+            .filter_map(|(point, eval)| {
+                if eval == max_evaluation {
+                    Some(point)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Some(*max_moves.choose(rng)?)
+    }
+
+    fn get_alpha_beta_move(&self, bot: Bot) -> Option<Point> {
+        let weights = self.get_weights(self.get_current_player(), bot);
+        let computer = self.get_current_player();
+        let depth = self.get_search_depth(computer, bot);
+        let breadth = self.get_search_breadth(computer, SEARCH_BREADTH);
+        let mut game = self.clone();
+
+        let mut best = None;
+        let mut alpha = -WIN_SCORE * 2;
+        for point in game.get_candidate_moves(computer, &weights, breadth) {
+            game.place(point, computer, None);
+            let score = -game.negamax(depth - 1, -WIN_SCORE * 2, -alpha, &weights, breadth);
+            game.unplace();
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(point);
+            }
+        }
+        best
+    }
+
+    /// Scores the position for the player to move, assuming both sides play the
+    /// move the search considers best from here on
+    fn negamax(
+        &mut self,
+        depth: usize,
+        mut alpha: i64,
+        beta: i64,
+        weights: &ComputerWeights,
+        breadth: usize,
+    ) -> i64 {
+        let player = match self.get_gamestate() {
+            // The previous player completed a line, the sooner the worse
+            GameState::PlayerWon(_) => return -WIN_SCORE - depth as i64,
+            GameState::Draw => return 0,
+            GameState::PlayerMove(player) => player,
+        };
+
+        if depth == 0 {
+            return self.evaluate_position(player, weights);
+        }
+
+        let mut best = -WIN_SCORE * 2;
+        for point in self.get_candidate_moves(player, weights, breadth) {
+            self.place(point, player, None);
+            let score = -self.negamax(depth - 1, -beta, -alpha, weights, breadth);
+            self.unplace();
+            best = i64::max(best, score);
+            alpha = i64::max(alpha, score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    /// The most promising valid moves for `player`, best first
+    fn get_candidate_moves(
+        &self,
+        player: Player,
+        weights: &ComputerWeights,
+        breadth: usize,
+    ) -> Vec<Point> {
+        let mut evals: Vec<_> = self
+            .get_valid_moves()
+            .into_iter()
+            .map(|point| (point, self.evaluate_location(point, player, weights)))
+            .collect();
+        evals.sort_by(|(_, a), (_, b)| b.cmp(a));
+        evals
+            .into_iter()
+            .take(breadth)
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    fn get_max_n_move(&self, bot: Bot) -> Option<Point> {
+        let weights = self.get_weights(self.get_current_player(), bot);
+        let computer = self.get_current_player();
+        let depth = self.get_search_depth(computer, bot);
+        let breadth = self.get_search_breadth(computer, MULTIPLAYER_SEARCH_BREADTH);
+        let mut game = self.clone();
+
+        let mut best: Option<(Point, i64)> = None;
+        for point in game.get_candidate_moves(computer, &weights, breadth) {
+            game.place(point, computer, None);
+            let score = game.max_n(depth - 1, &weights, breadth)[computer];
+            game.unplace();
+            if best.is_none_or(|(_, best)| score > best) {
+                best = Some((point, score));
+            }
+        }
+        best.map(|(point, _)| point)
+    }
+
+    /// Scores the position for every player, assuming each player picks the move
+    /// that is best for themselves
+    fn max_n(&mut self, depth: usize, weights: &ComputerWeights, breadth: usize) -> Vec<i64> {
+        let player = match self.get_gamestate() {
+            GameState::PlayerWon(win) => {
+                return (0..self.get_player_count())
+                    .map(|player| {
+                        if player == win.player {
+                            WIN_SCORE + depth as i64
+                        } else {
+                            -WIN_SCORE - depth as i64
+                        }
+                    })
+                    .collect()
+            }
+            GameState::Draw => return vec![0; self.get_player_count()],
+            GameState::PlayerMove(player) => player,
+        };
+
+        if depth == 0 {
+            return self.evaluate_players(weights);
+        }
+
+        let mut best: Option<Vec<i64>> = None;
+        for point in self.get_candidate_moves(player, weights, breadth) {
+            self.place(point, player, None);
+            let scores = self.max_n(depth - 1, weights, breadth);
+            self.unplace();
+            if best
+                .as_ref()
+                .is_none_or(|best| scores[player] > best[player])
+            {
+                best = Some(scores);
+            }
+        }
+        best.unwrap_or_else(|| vec![0; self.get_player_count()])
+    }
+
+    fn get_paranoid_move(&self, bot: Bot) -> Option<Point> {
+        let weights = self.get_weights(self.get_current_player(), bot);
+        let computer = self.get_current_player();
+        let depth = self.get_search_depth(computer, bot);
+        let breadth = self.get_search_breadth(computer, MULTIPLAYER_SEARCH_BREADTH);
+        let mut game = self.clone();
+
+        let mut best = None;
+        let mut alpha = -WIN_SCORE * 2;
+        for point in game.get_candidate_moves(computer, &weights, breadth) {
+            game.place(point, computer, None);
+            let score = game.paranoid(computer, depth - 1, alpha, WIN_SCORE * 2, &weights, breadth);
+            game.unplace();
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(point);
+            }
+        }
+        best
+    }
+
+    /// Scores the position for `computer`, assuming every other player works
+    /// together to minimise that score
+    fn paranoid(
+        &mut self,
+        computer: Player,
+        depth: usize,
+        mut alpha: i64,
+        mut beta: i64,
+        weights: &ComputerWeights,
+        breadth: usize,
+    ) -> i64 {
+        let player = match self.get_gamestate() {
+            GameState::PlayerWon(win) if win.player == computer => return WIN_SCORE + depth as i64,
+            GameState::PlayerWon(_) => return -WIN_SCORE - depth as i64,
+            GameState::Draw => return 0,
+            GameState::PlayerMove(player) => player,
+        };
+
+        if depth == 0 {
+            return self.evaluate_players(weights)[computer];
+        }
+
+        let maximizing = player == computer;
+        let mut best = if maximizing {
+            -WIN_SCORE * 2
+        } else {
+            WIN_SCORE * 2
+        };
+        for point in self.get_candidate_moves(player, weights, breadth) {
+            self.place(point, player, None);
+            let score = self.paranoid(computer, depth - 1, alpha, beta, weights, breadth);
+            self.unplace();
+            if maximizing {
+                best = i64::max(best, score);
+                alpha = i64::max(alpha, score);
+            } else {
+                best = i64::min(best, score);
+                beta = i64::min(beta, score);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    /// Searches for `bot`'s move with Monte Carlo tree search, regardless of the
+    /// bot's engine, running playouts until `budget` is spent
+    pub fn get_monte_carlo_move<R: Rng + ?Sized>(
+        &self,
+        bot: Bot,
+        budget: Budget,
+        playout: Playout,
+        rng: &mut R,
+    ) -> Option<Point> {
+        let weights = self.get_weights(self.get_current_player(), bot);
+        let mut game = self.clone();
+        let mut tree = vec![Node::new(None, None, &game)];
+        let start = Instant::now();
+        let mut iterations = 0;
+
+        while match budget {
+            Budget::Iterations(limit) => {
+                iterations < limit && (iterations == 0 || start.elapsed() < MONTE_CARLO_TIME_LIMIT)
+            }
+            Budget::Time(limit) => iterations == 0 || start.elapsed() < limit,
+        } {
+            iterations += 1;
+            let mut placed = 0;
+
+            // Selection: follow the most promising children down to a leaf
+            let mut node = 0;
+            while tree[node].untried.is_empty() && !tree[node].children.is_empty() {
+                let parent_visits = tree[node].visits as f64;
+                node = *tree[node]
+                    .children
+                    .iter()
+                    .max_by(|a, b| {
+                        tree[**a]
+                            .get_priority(parent_visits)
+                            .total_cmp(&tree[**b].get_priority(parent_visits))
+                    })
+                    .unwrap();
+                let (point, player) = tree[node].action.unwrap();
+                game.place(point, player, None);
+                placed += 1;
+            }
+
+            // Expansion: add one of the moves not yet tried from this node
+            if let GameState::PlayerMove(player) = game.get_gamestate() {
+                if !tree[node].untried.is_empty() {
+                    let index = rng.gen_range(0..tree[node].untried.len());
+                    let point = tree[node].untried.swap_remove(index);
+                    game.place(point, player, None);
+                    placed += 1;
+                    tree.push(Node::new(Some((point, player)), Some(node), &game));
+                    let child = tree.len() - 1;
+                    tree[node].children.push(child);
+                    node = child;
+                }
+            }
+
+            // Simulation: play the game out to the end, keeping the open moves
+            // up to date rather than listing them again after every move
+            let mut moves = game.get_valid_moves();
+            while let GameState::PlayerMove(player) = game.get_gamestate() {
+                let index = game.get_playout_move(&moves, player, playout, &weights, rng);
+                let point = moves.swap_remove(index);
+                game.place(point, player, None);
+                placed += 1;
+                if game.get_placement() == Placement::Gravity && point.y > 0 {
+                    moves.push(Point::new(point.x, point.y - 1));
+                }
+            }
+            let winner = match game.get_gamestate() {
+                GameState::PlayerWon(win) => Some(win.player),
+                _ => None,
+            };
+            for _ in 0..placed {
+                game.unplace();
+            }
+
+            // Backpropagation: credit the result to every node on the path
+            let mut current = Some(node);
+            while let Some(index) = current {
+                let node = &mut tree[index];
+                node.visits += 1;
+                node.reward += match (winner, node.action) {
+                    (Some(winner), Some((_, player))) if winner == player => 1.0,
+                    (None, _) => 1.0 / self.get_player_count() as f64,
+                    _ => 0.0,
+                };
+                current = node.parent;
+            }
+        }
+
+        tree[0]
+            .children
+            .iter()
+            .max_by_key(|child| tree[**child].visits)
+            .and_then(|child| tree[*child].action)
+            .map(|(point, _)| point)
+    }
+
+    /// Which of `moves` a playout plays next, by its index
+    fn get_playout_move<R: Rng + ?Sized>(
+        &self,
+        moves: &[Point],
+        player: Player,
+        playout: Playout,
+        weights: &ComputerWeights,
+        rng: &mut R,
+    ) -> usize {
+        match playout {
+            Playout::Random => rng.gen_range(0..moves.len()),
+            Playout::Guided => {
+                index::sample(rng, moves.len(), GUIDED_PLAYOUT_SAMPLES.min(moves.len()))
+                    .into_iter()
+                    .max_by_key(|&index| self.evaluate_location(moves[index], player, weights))
+                    .unwrap()
+            }
+        }
+    }
+
+    /// The heuristic value of the best move `player` has available
+    fn get_best_location_score(&self, player: Player, weights: &ComputerWeights) -> i64 {
+        self.get_valid_moves()
+            .into_iter()
+            .map(|point| self.evaluate_location(point, player, weights))
+            .max()
+            .unwrap_or(0)
+            .min((WIN_SCORE / 2) as u64) as i64
+    }
+
+    /// Leaf heuristic: how much better the best move available to `player` is
+    /// than the best move available to the player after them
+    fn evaluate_position(&self, player: Player, weights: &ComputerWeights) -> i64 {
+        self.get_best_location_score(player, weights)
+            - self.get_best_location_score((player + 1) % self.get_player_count(), weights)
+    }
+
+    /// Multi-player leaf heuristic: how far each player's best move is ahead of
+    /// the strongest of the other players'
+    fn evaluate_players(&self, weights: &ComputerWeights) -> Vec<i64> {
+        let scores: Vec<_> = (0..self.get_player_count())
+            .map(|player| self.get_best_location_score(player, weights))
+            .collect();
+        scores
+            .iter()
+            .enumerate()
+            .map(|(player, score)| {
+                let strongest_other = scores
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != player)
+                    .map(|(_, score)| *score)
+                    .max()
+                    .unwrap_or(0);
+                score - strongest_other
+            })
+            .collect()
+    }
+
+    /// The heuristic score of `player` moving to each cell, as the bots see it
+    /// with `weights`. Laid out like [`GameTrait::get_board`], with `None` for
+    /// occupied cells.
+    pub fn get_location_scores(
+        &self,
+        player: Player,
+        weights: &ComputerWeights,
+    ) -> Vec<Vec<Option<u64>>> {
+        self.get_board()
+            .into_iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(x, tile)| {
+                        let point = Point::new(x as isize, y as isize);
+                        tile.is_none()
+                            .then(|| self.evaluate_location(point, player, weights))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn evaluate_location(&self, point: Point, computer: usize, weights: &ComputerWeights) -> u64 {
+        let mut eval = 0;
+        let goal = self.get_goal();
+        let player_count = self.get_player_count();
+
+        for direction in DIRECTIONS {
+            let mut initial_empty = 0;
+            let mut empty = vec![0; player_count];
+            let mut count = vec![0; player_count];
+
+            for d in [-1, 1] {
+                let mut step = ScannerStep::InitialEmpty;
+                for i in 1..(self.get_goal()) {
+                    match self.get_tile(&(point + (direction * d * i))) {
+                        Ok(tile) => match step {
+                            ScannerStep::InitialEmpty => {
+                                if let Some(tile_player) = tile {
+                                    step = ScannerStep::Player(tile_player);
+                                    count[tile_player] += 1;
+                                } else {
+                                    initial_empty += 1;
+                                }
+                            }
+                            ScannerStep::Player(player) => {
+                                if let Some(tile_player) = tile {
+                                    if tile_player == player {
+                                        count[player] += 1;
+                                    } else {
+                                        break;
+                                    }
+                                } else {
+                                    empty[player] += 1;
+                                }
+                            }
+                        },
+                        Err(_) => {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            for player in 0..player_count {
+                let empty = initial_empty + empty[player];
+                let count = count[player];
+
+                if empty as isize + count as isize >= goal - 1 {
+                    let overall_weight = if player == computer {
+                        weights.computer
+                    } else {
+                        weights.opponent
+                    };
+                    let streak_weight = if player == computer {
+                        weights.streak_computer
+                    } else {
+                        weights.streak_opponent
+                    };
+
+                    eval = u64::saturating_add(
+                        eval,
+                        overall_weight.saturating_mul(u64::saturating_add(
+                            weights.empty.saturating_mul(empty),
+                            weights
+                                .populated
+                                .saturating_mul(count)
+                                .saturating_mul(streak_weight.saturating_pow(count as u32)),
+                        )),
+                    );
+                }
+            }
+        }
+
+        eval
+    }
+}
+
+/// A position in the Monte Carlo search tree
+struct Node {
+    /// The move that led here, and the player who made it
+    action: Option<(Point, Player)>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<Point>,
+    visits: u32,
+    /// Total result of the playouts through here, for the player who moved
+    reward: f64,
+}
+
+impl Node {
+    fn new(action: Option<(Point, Player)>, parent: Option<usize>, game: &Game) -> Self {
+        Self {
+            action,
+            parent,
+            children: Vec::new(),
+            untried: match game.get_gamestate() {
+                GameState::PlayerMove(_) => game.get_valid_moves(),
+                _ => Vec::new(),
+            },
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+
+    /// Upper confidence bound used to balance exploiting good moves against
+    /// exploring rarely visited ones
+    fn get_priority(&self, parent_visits: f64) -> f64 {
+        let visits = self.visits as f64;
+        self.reward / visits + EXPLORATION * (parent_visits.ln() / visits).sqrt()
+    }
+}
+
+enum ScannerStep {
+    InitialEmpty,
+    Player(Player),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlayerType;
+
+    /// A standard two player game after the given columns were played
+    fn game_after(columns: &[isize]) -> Game {
+        let mut game = Game::new(7, 6, 4, vec![PlayerType::User; 2], Placement::Gravity).unwrap();
+        for &column in columns {
+            game.play_column(column).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn alpha_beta_takes_an_immediate_win() {
+        // Both players have three in a row, the first on the bottom row
+        let game = game_after(&[0, 0, 1, 1, 2, 2]);
+        let bot = Bot(Difficulty::Normal, Strategy::Defensive, Engine::AlphaBeta);
+        assert_eq!(game.get_computer_move(bot), Some(Point::new(3, 5)));
+    }
+
+    #[test]
+    fn alpha_beta_blocks_a_forced_loss() {
+        // The second player wins in column 3 unless the first plays there now
+        let game = game_after(&[6, 0, 6, 1, 5, 2]);
+        let bot = Bot(Difficulty::Normal, Strategy::Offensive, Engine::AlphaBeta);
+        assert_eq!(game.get_computer_move(bot), Some(Point::new(3, 5)));
+    }
+}
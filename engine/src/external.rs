@@ -0,0 +1,454 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::OpenOptions,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Bot, Game, GameTrait, NewGameOptions, Placement, Point, ProtocolError, Request, Response,
+    SearchLimits, PROTOCOL_VERSION,
+};
+
+/// How long an engine gets to answer when its command does not say
+pub const DEFAULT_ENGINE_TIMEOUT: u64 = 10_000;
+
+/// How long an engine gets to exit by itself before it is killed
+const EXIT_GRACE: Duration = Duration::from_millis(500);
+
+fn default_timeout() -> u64 {
+    DEFAULT_ENGINE_TIMEOUT
+}
+
+/// How to start a program that plays through the protocol described on
+/// [`Request`]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct EngineCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Longest to wait for each answer, in milliseconds
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    /// File every line sent to and from the engine is added to
+    #[serde(default)]
+    pub log: Option<PathBuf>,
+}
+
+impl Display for EngineCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.program)?;
+        self.args.iter().try_for_each(|arg| write!(f, " {arg}"))
+    }
+}
+
+/// Parses a program followed by its arguments, separated by spaces
+impl FromStr for EngineCommand {
+    type Err = ExternalEngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace().map(String::from);
+        Ok(Self {
+            program: words.next().ok_or(ExternalEngineError::NoProgram)?,
+            args: words.collect(),
+            timeout: DEFAULT_ENGINE_TIMEOUT,
+            log: None,
+        })
+    }
+}
+
+/// Why an external engine did not come up with a move
+#[derive(Debug)]
+pub enum ExternalEngineError {
+    NoProgram,
+    Spawn(io::Error),
+    Io(io::Error),
+    /// The engine stopped, with its exit status if it has one
+    Exited(Option<ExitStatus>),
+    Timeout(Duration),
+    Protocol(ProtocolError),
+    UnsupportedVersion(u32),
+    /// The engine answered a request with an error
+    Refused(String),
+    Unexpected(Response),
+    IllegalMove(Point),
+}
+
+impl Display for ExternalEngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoProgram => f.write_str("No engine program was given"),
+            Self::Spawn(error) => write!(f, "Could not start the engine: {error}"),
+            Self::Io(error) => write!(f, "Could not talk to the engine: {error}"),
+            Self::Exited(Some(status)) => write!(f, "The engine stopped with {status}"),
+            Self::Exited(None) => f.write_str("The engine stopped"),
+            Self::Timeout(timeout) => {
+                write!(f, "The engine did not answer within {timeout:?}")
+            }
+            Self::Protocol(error) => write!(f, "The engine's answer made no sense: {error}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "The engine speaks protocol version {version}, but only version {PROTOCOL_VERSION} is supported"
+            ),
+            Self::Refused(message) => write!(f, "The engine refused: {message}"),
+            Self::Unexpected(response) => write!(f, "The engine answered {response} out of turn"),
+            Self::IllegalMove(point) => {
+                write!(f, "The engine chose {} {}, which can't be played", point.x, point.y)
+            }
+        }
+    }
+}
+
+impl Error for ExternalEngineError {}
+
+/// A running engine program
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines from the engine, read on their own thread so waits can time out
+    lines: Receiver<io::Result<String>>,
+    /// The settings and moves of the game the engine was last told about
+    synced: Option<(NewGameOptions, Vec<Point>)>,
+}
+
+/// An engine program playing through the protocol. It is started when it is
+/// first asked for a move, and stopped whenever it fails so the next move
+/// starts it afresh.
+///
+/// The engine is asked `protocol` once it starts, is kept up to date with
+/// `newgame` and `move`, and is asked `go` for each of its moves with a `time`
+/// a little under its timeout, so those requests and `quit` are all it has to
+/// understand.
+pub struct ExternalEngine {
+    command: EngineCommand,
+    process: Option<Process>,
+}
+
+impl ExternalEngine {
+    pub fn new(command: EngineCommand) -> Self {
+        Self {
+            command,
+            process: None,
+        }
+    }
+
+    pub fn get_command(&self) -> &EngineCommand {
+        &self.command
+    }
+
+    /// The engine's move for the player to move in `game`, asking it to search
+    /// as `bot` would
+    pub fn get_move(&mut self, game: &Game, bot: Bot) -> Result<Point, ExternalEngineError> {
+        let result = self.try_get_move(game, bot);
+        if let Err(error) = &result {
+            log(&self.command, &format!("! {error}"));
+            self.stop();
+        }
+        result
+    }
+
+    fn try_get_move(&mut self, game: &Game, bot: Bot) -> Result<Point, ExternalEngineError> {
+        if self.process.is_none() {
+            self.start()?;
+        }
+        self.sync(game)?;
+        // A tenth of the timeout is kept back for the answer to arrive in
+        let timeout = self.command.timeout;
+        match self.request(Request::Go(SearchLimits {
+            bot,
+            depth: None,
+            time: Some(Duration::from_millis(timeout - timeout / 10)),
+        }))? {
+            Response::BestMove(Some(point)) if game.get_valid_moves().contains(&point) => Ok(point),
+            Response::BestMove(Some(point)) => Err(ExternalEngineError::IllegalMove(point)),
+            response => Err(ExternalEngineError::Unexpected(response)),
+        }
+    }
+
+    fn start(&mut self) -> Result<(), ExternalEngineError> {
+        log(&self.command, &format!("* starting {}", self.command));
+        let mut child = Command::new(&self.command.program)
+            .args(&self.command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(ExternalEngineError::Spawn)?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.process = Some(Process {
+            child,
+            stdin,
+            lines,
+            synced: None,
+        });
+        match self.request(Request::Protocol)? {
+            Response::Protocol(PROTOCOL_VERSION) => Ok(()),
+            Response::Protocol(version) => Err(ExternalEngineError::UnsupportedVersion(version)),
+            response => Err(ExternalEngineError::Unexpected(response)),
+        }
+    }
+
+    /// Tells the engine about the moves played since it was last asked, or
+    /// about the whole game if it has changed in any other way
+    fn sync(&mut self, game: &Game) -> Result<(), ExternalEngineError> {
+        let options = NewGameOptions {
+            width: game.get_width(),
+            height: game.get_height(),
+            goal: game.get_goal(),
+            players: game.get_player_count(),
+            placement: game.get_placement(),
+            seed: Some(game.get_seed()),
+        };
+        let moves: Vec<_> = game.get_history().iter().map(|m| m.point).collect();
+
+        let known = match self
+            .process
+            .as_ref()
+            .and_then(|process| process.synced.as_ref())
+        {
+            Some((synced, known)) if *synced == options && moves.starts_with(known) => known.len(),
+            _ => {
+                self.expect_ok(Request::NewGame(options))?;
+                0
+            }
+        };
+        for point in &moves[known..] {
+            let y = (options.placement == Placement::Free).then_some(point.y);
+            self.expect_ok(Request::Move(point.x, y))?;
+        }
+
+        if let Some(process) = &mut self.process {
+            process.synced = Some((options, moves));
+        }
+        Ok(())
+    }
+
+    fn expect_ok(&mut self, request: Request) -> Result<(), ExternalEngineError> {
+        match self.request(request)? {
+            Response::Ok => Ok(()),
+            response => Err(ExternalEngineError::Unexpected(response)),
+        }
+    }
+
+    /// Sends `request` and waits for the answer
+    fn request(&mut self, request: Request) -> Result<Response, ExternalEngineError> {
+        let process = self
+            .process
+            .as_mut()
+            .ok_or(ExternalEngineError::Exited(None))?;
+        log(&self.command, &format!("> {request}"));
+        writeln!(process.stdin, "{request}")
+            .and_then(|()| process.stdin.flush())
+            .map_err(ExternalEngineError::Io)?;
+
+        let timeout = Duration::from_millis(self.command.timeout);
+        let line = match process.lines.recv_timeout(timeout) {
+            Ok(line) => line.map_err(ExternalEngineError::Io)?,
+            Err(RecvTimeoutError::Timeout) => return Err(ExternalEngineError::Timeout(timeout)),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(ExternalEngineError::Exited(wait_for_exit(
+                    &mut process.child,
+                )))
+            }
+        };
+        log(&self.command, &format!("< {line}"));
+
+        match line.parse().map_err(ExternalEngineError::Protocol)? {
+            Response::Error(message) => Err(ExternalEngineError::Refused(message)),
+            response => Ok(response),
+        }
+    }
+
+    /// Asks the engine to quit, killing it if it hasn't within [`EXIT_GRACE`]
+    fn stop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            log(&self.command, "* stopping");
+            writeln!(process.stdin, "{}", Request::Quit)
+                .and_then(|()| process.stdin.flush())
+                .ok();
+            if wait_for_exit(&mut process.child).is_none() {
+                log(&self.command, "* killing");
+                process.child.kill().ok();
+                process.child.wait().ok();
+            }
+        }
+    }
+}
+
+impl Drop for ExternalEngine {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The engine's exit status once it exits, or `None` if it is still running
+/// after [`EXIT_GRACE`]
+fn wait_for_exit(child: &mut Child) -> Option<ExitStatus> {
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if start.elapsed() < EXIT_GRACE => thread::sleep(Duration::from_millis(10)),
+            _ => return None,
+        }
+    }
+}
+
+/// Adds a line to the command's log, if it has one. Logging is best effort, so
+/// a log that can't be written never stops a game.
+fn log(command: &EngineCommand, line: &str) {
+    if let Some(path) = &command.log {
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+            writeln!(file, "[{}] {line}", command.program).ok();
+        }
+    }
+}
+
+impl Game {
+    /// The move `engine` plays for the current player, or the move of `bot` if
+    /// the engine fails, along with why it failed
+    pub fn get_external_move(
+        &self,
+        engine: &mut ExternalEngine,
+        bot: Bot,
+    ) -> (Option<Point>, Option<ExternalEngineError>) {
+        match engine.get_move(self, bot) {
+            Ok(point) => (Some(point), None),
+            Err(error) => (self.get_computer_move(bot), Some(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Placement, PlayerType};
+
+    /// The mock engine from `scripts`, started with `flags`
+    fn mock_engine(flags: &[&str], timeout: u64) -> ExternalEngine {
+        let script = concat!(env!("CARGO_MANIFEST_DIR"), "/../scripts/mock_engine.py");
+        ExternalEngine::new(EngineCommand {
+            program: String::from("python3"),
+            args: [script, "--seed", "1"]
+                .iter()
+                .chain(flags)
+                .map(|arg| arg.to_string())
+                .collect(),
+            timeout,
+            log: None,
+        })
+    }
+
+    fn new_game() -> Game {
+        Game::new(7, 6, 4, vec![PlayerType::User; 2], Placement::Gravity).unwrap()
+    }
+
+    #[test]
+    fn plays_legal_moves_through_the_game() {
+        let mut engine = mock_engine(&[], 2000);
+        let mut game = new_game();
+        for _ in 0..4 {
+            let point = engine.get_move(&game, Bot::default()).unwrap();
+            game.play_move(point).unwrap();
+        }
+    }
+
+    #[test]
+    fn a_crash_is_reported_as_an_exit() {
+        let mut engine = mock_engine(&["--crash-after", "1"], 2000);
+        let mut game = new_game();
+        let point = engine.get_move(&game, Bot::default()).unwrap();
+        game.play_move(point).unwrap();
+        let error = engine.get_move(&game, Bot::default()).unwrap_err();
+        assert!(
+            matches!(&error, ExternalEngineError::Exited(Some(status)) if status.code() == Some(3)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn a_hang_times_out() {
+        let mut engine = mock_engine(&["--hang-after", "0"], 300);
+        let error = engine.get_move(&new_game(), Bot::default()).unwrap_err();
+        assert!(
+            matches!(error, ExternalEngineError::Timeout(_)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn a_slow_answer_times_out() {
+        let mut engine = mock_engine(&["--delay", "1000"], 300);
+        let error = engine.get_move(&new_game(), Bot::default()).unwrap_err();
+        assert!(
+            matches!(error, ExternalEngineError::Timeout(_)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn closing_stdout_is_reported_as_an_exit() {
+        let mut engine = mock_engine(&["--close-after", "0"], 5000);
+        let start = Instant::now();
+        let error = engine.get_move(&new_game(), Bot::default()).unwrap_err();
+        assert!(matches!(error, ExternalEngineError::Exited(_)), "{error:?}");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn an_illegal_move_is_rejected() {
+        let mut engine = mock_engine(&["--illegal"], 2000);
+        let mut game = new_game();
+        game.play_column(3).unwrap();
+        let error = engine.get_move(&game, Bot::default()).unwrap_err();
+        assert!(
+            matches!(error, ExternalEngineError::IllegalMove(point) if point == Point::new(3, 5)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn another_protocol_version_is_refused() {
+        let mut engine = mock_engine(&["--version", "2"], 2000);
+        let error = engine.get_move(&new_game(), Bot::default()).unwrap_err();
+        assert!(
+            matches!(error, ExternalEngineError::UnsupportedVersion(2)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn a_missing_program_fails_to_start() {
+        let mut engine = ExternalEngine::new("no-such-engine-program".parse().unwrap());
+        let error = engine.get_move(&new_game(), Bot::default()).unwrap_err();
+        assert!(matches!(error, ExternalEngineError::Spawn(_)), "{error:?}");
+    }
+
+    #[test]
+    fn the_bot_moves_when_the_engine_fails() {
+        let mut engine = mock_engine(&["--crash-after", "0"], 2000);
+        let game = new_game();
+        let (point, error) = game.get_external_move(&mut engine, Bot::default());
+        assert!(point.is_some_and(|point| game.get_valid_moves().contains(&point)));
+        assert!(matches!(error, Some(ExternalEngineError::Exited(_))));
+    }
+}
@@ -0,0 +1,734 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, Mul},
+    str::FromStr,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    BitBoard, Bot, ComputerWeights, Difficulty, Engine, EngineCommand, GameState, GameTrait, Line,
+    Move, ParseNameError, Placement, Player, Point, Profile, Strategy, WeightTable, Win,
+};
+
+/// Longest side a board can have, keeping the bitboards and the bots' work
+/// on every move small
+pub const MAX_BOARD_SIZE: isize = 100;
+
+pub(crate) const DIRECTIONS: [Point; 4] = [
+    Point { x: 1, y: 0 },
+    Point { x: -1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: 1, y: 1 },
+];
+
+/// A game in progress: the board, the players and the moves played so far
+#[derive(Clone, Debug)]
+pub struct Game {
+    boards: Vec<BitBoard>,
+    occupied: BitBoard,
+    history: Vec<Move>,
+    redo_stack: Vec<Move>,
+    state: GameState,
+    width: isize,
+    height: isize,
+    goal: isize,
+    players: Vec<PlayerType>,
+    placement: Placement,
+    /// Seeds the randomness of every computer move, so the same seed and the
+    /// same moves by people always lead to the same game
+    seed: u64,
+    /// Weights the bots play with, by difficulty and strategy
+    weight_table: Arc<WeightTable>,
+    /// Profiles that replace the weights and search limits of a player's bot
+    player_profiles: Vec<Option<Profile>>,
+    /// How many hints each player has asked for this game
+    hints: Vec<usize>,
+    /// The programs playing for [`PlayerType::External`] players
+    player_engines: Vec<Option<EngineCommand>>,
+}
+
+/// Who chooses the moves for a player
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum PlayerType {
+    User,
+    Computer(Bot),
+    /// A program set with [`Game::set_player_engine`], with the bot moving in
+    /// its place whenever it fails
+    External(Bot),
+    /// A person playing from another machine, through a [`GameHost`](crate::GameHost)
+    Remote,
+}
+
+impl PlayerType {
+    /// The bot choosing the moves, or the one an external engine falls back to
+    pub fn get_bot(&self) -> Option<&Bot> {
+        match self {
+            Self::User | Self::Remote => None,
+            Self::Computer(bot) | Self::External(bot) => Some(bot),
+        }
+    }
+
+    fn map_bot(self, f: impl FnOnce(Bot) -> Bot) -> Self {
+        match self {
+            Self::User | Self::Remote => self,
+            Self::Computer(bot) => Self::Computer(f(bot)),
+            Self::External(bot) => Self::External(f(bot)),
+        }
+    }
+
+    pub fn set_difficulty(self, difficulty: Difficulty) -> Self {
+        self.map_bot(|Bot(_, strategy, engine)| Bot(difficulty, strategy, engine))
+    }
+
+    pub fn set_strategy(self, strategy: Strategy) -> Self {
+        self.map_bot(|Bot(difficulty, _, engine)| Bot(difficulty, strategy, engine))
+    }
+
+    pub fn set_engine(self, engine: Engine) -> Self {
+        self.map_bot(|Bot(difficulty, strategy, _)| Bot(difficulty, strategy, engine))
+    }
+}
+
+impl Display for PlayerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::User => f.write_str("User"),
+            Self::Computer(bot) => write!(f, "{bot}"),
+            Self::External(bot) => write!(f, "External ({bot} if it fails)"),
+            Self::Remote => f.write_str("Remote"),
+        }
+    }
+}
+
+/// Parses `user`, or a bot in the form [`Bot`] is parsed from
+impl FromStr for PlayerType {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("user") {
+            Ok(Self::User)
+        } else {
+            s.parse().map(Self::Computer)
+        }
+    }
+}
+
+impl Game {
+    /// Starts a game on a `width` by `height` board, won by the first player to
+    /// get `goal` pieces in a row. Players take turns in the order given.
+    pub fn new(
+        width: isize,
+        height: isize,
+        goal: isize,
+        players: Vec<PlayerType>,
+        placement: Placement,
+    ) -> Result<Self, NewGameError> {
+        if let Some(error) = Self::validate(width, height, goal, players.len())
+            .into_iter()
+            .chain(Self::validate_players(&players))
+            .next()
+        {
+            Err(error)
+        } else {
+            Ok(Self {
+                boards: vec![BitBoard::new(width, height); players.len()],
+                occupied: BitBoard::new(width, height),
+                player_profiles: vec![None; players.len()],
+                hints: vec![0; players.len()],
+                player_engines: vec![None; players.len()],
+                history: Vec::new(),
+                redo_stack: Vec::new(),
+                state: GameState::PlayerMove(0),
+                width,
+                height,
+                goal,
+                players,
+                placement,
+                seed: rand::random(),
+                weight_table: Arc::default(),
+            })
+        }
+    }
+
+    pub(crate) fn place(&mut self, point: Point, player: Player, bot: Option<Bot>) {
+        self.boards[player].set(&point);
+        self.occupied.set(&point);
+        self.history.push(Move { point, player, bot });
+        self.state = self.check_move(point, player);
+    }
+
+    /// Takes back the last move placed, without touching the redo stack
+    pub(crate) fn unplace(&mut self) -> Option<Move> {
+        let m = self.history.pop()?;
+        self.boards[m.player].unset(&m.point);
+        self.occupied.unset(&m.point);
+        // A move can only have been played on its player's turn
+        self.state = GameState::PlayerMove(m.player);
+        Some(m)
+    }
+
+    /// Works out the state after `player` moved to `point`, which can only have
+    /// completed lines running through that point
+    fn check_move(&self, point: Point, player: Player) -> GameState {
+        let board = &self.boards[player];
+        let lines: Vec<_> = DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| {
+                let before = (1isize..)
+                    .take_while(|i| board.get(&point.add(direction.mul(-i))))
+                    .count() as isize;
+                let after = (1..)
+                    .take_while(|i| board.get(&point.add(direction.mul(*i))))
+                    .count() as isize;
+                let length = before + after + 1;
+                (length >= self.goal).then(|| Line {
+                    start: point.add(direction.mul(-before)),
+                    direction,
+                    length,
+                })
+            })
+            .collect();
+
+        if !lines.is_empty() {
+            GameState::PlayerWon(Win { player, lines })
+        } else if self.history.len() < (self.width * self.height) as usize {
+            GameState::PlayerMove(self.get_current_player())
+        } else {
+            GameState::Draw
+        }
+    }
+
+    /// Whether `player` would complete a line by moving to the empty `point`
+    pub(crate) fn wins_at(&self, point: Point, player: Player) -> bool {
+        matches!(self.check_move(point, player), GameState::PlayerWon(_))
+    }
+
+    /// Works out the state from scratch by searching every player's board for runs
+    fn scan_gamestate(&self) -> GameState {
+        let mut lines = Vec::new();
+        for (player, board) in self.boards.iter().enumerate() {
+            for direction in DIRECTIONS {
+                let runs = board.runs(&direction, self.goal);
+                for start in runs.points() {
+                    // Only report each run once, from its first cell
+                    if board.get(&start.add(direction.mul(-1))) {
+                        continue;
+                    }
+                    let length = (1..)
+                        .take_while(|i| board.get(&start.add(direction.mul(*i))))
+                        .count() as isize
+                        + 1;
+                    lines.push((
+                        player,
+                        Line {
+                            start,
+                            direction,
+                            length,
+                        },
+                    ));
+                }
+            }
+        }
+
+        let winner = self
+            .history
+            .iter()
+            .rev()
+            .map(|m| m.player)
+            .find(|player| lines.iter().any(|(line_player, _)| line_player == player));
+
+        if let Some(player) = winner {
+            return GameState::PlayerWon(Win {
+                player,
+                lines: lines
+                    .into_iter()
+                    .filter(|(line_player, _)| *line_player == player)
+                    .map(|(_, line)| line)
+                    .collect(),
+            });
+        }
+
+        if self.history.len() < (self.width * self.height) as usize {
+            GameState::PlayerMove(self.get_current_player())
+        } else {
+            GameState::Draw
+        }
+    }
+
+    pub fn get_players(&self) -> &[PlayerType] {
+        &self.players
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Replaces the random seed the game started with
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn get_weight_table(&self) -> &Arc<WeightTable> {
+        &self.weight_table
+    }
+
+    /// Replaces the built-in weights for every bot in the game
+    pub fn set_weight_table(&mut self, weight_table: Arc<WeightTable>) {
+        self.weight_table = weight_table;
+    }
+
+    /// Makes the bot playing as `player` use the weights and search limits of
+    /// `profile`, whatever its difficulty and strategy, or go back to the
+    /// weight table with `None`
+    pub fn set_player_profile(&mut self, player: Player, profile: Option<Profile>) {
+        self.player_profiles[player] = profile;
+    }
+
+    pub fn get_player_profile(&self, player: Player) -> Option<&Profile> {
+        self.player_profiles.get(player)?.as_ref()
+    }
+
+    /// Makes an external player play through the program `command`
+    pub fn set_player_engine(&mut self, player: Player, command: Option<EngineCommand>) {
+        self.player_engines[player] = command;
+    }
+
+    pub fn get_player_engine(&self, player: Player) -> Option<&EngineCommand> {
+        self.player_engines.get(player)?.as_ref()
+    }
+
+    /// The weights `bot` uses when it plays as `player`
+    pub fn get_weights(&self, player: Player, bot: Bot) -> ComputerWeights {
+        self.get_player_profile(player).map_or_else(
+            || self.weight_table.get_bot_weights(bot),
+            |profile| profile.weights,
+        )
+    }
+
+    /// How many hints each player has asked for this game
+    pub fn get_hint_counts(&self) -> &[usize] {
+        &self.hints
+    }
+
+    /// Counts a hint given to `player`
+    pub fn record_hint(&mut self, player: Player) {
+        self.hints[player] += 1;
+    }
+
+    pub(crate) fn set_hint_counts(&mut self, hints: Vec<usize>) {
+        self.hints = hints;
+        self.hints.resize(self.players.len(), 0);
+    }
+
+    /// The computer player whose turn it is, if the game is waiting on one. For
+    /// an external player this is the bot it falls back to.
+    pub fn get_waiting_bot(&self) -> Option<Bot> {
+        match self.get_gamestate() {
+            GameState::PlayerMove(player) => self.players[player].get_bot().copied(),
+            _ => None,
+        }
+    }
+
+    /// Plays a move chosen by the computer player whose turn it is
+    pub fn play_computer_move(&mut self, point: Point) -> Result<(), super::PlayMoveError> {
+        match (self.get_gamestate(), self.get_waiting_bot()) {
+            (GameState::PlayerMove(player), Some(bot)) => {
+                let point = self.get_landing_point(&point)?;
+                self.place(point, player, Some(bot));
+                self.redo_stack.clear();
+                Ok(())
+            }
+            (state, _) => Err(super::PlayMoveError::InvalidGameState(state)),
+        }
+    }
+
+    /// Every reason the settings cannot make a game, in the order [`Game::new`]
+    /// reports them
+    pub fn validate(
+        width: isize,
+        height: isize,
+        goal: isize,
+        player_count: usize,
+    ) -> Vec<NewGameError> {
+        [
+            (player_count < 2, NewGameError::PlayersMustBeAtLeast2),
+            (width < 2, NewGameError::WidthMustBeAtLeast2),
+            (height < 2, NewGameError::HeightMustBeAtLeast2),
+            (width > MAX_BOARD_SIZE, NewGameError::WidthTooLarge),
+            (height > MAX_BOARD_SIZE, NewGameError::HeightTooLarge),
+            (goal < 2, NewGameError::GoalMustBeAtLeast2),
+            (goal >= height, NewGameError::GoalMustBeLessThanHeight),
+            (goal >= width, NewGameError::GoalMustBeLessThanWidth),
+        ]
+        .into_iter()
+        .filter_map(|(invalid, error)| invalid.then_some(error))
+        .collect()
+    }
+
+    /// Every reason `players` cannot play a game together, after those from
+    /// [`Game::validate`]
+    pub fn validate_players(players: &[PlayerType]) -> Vec<NewGameError> {
+        let unsupported = players
+            .iter()
+            .filter_map(PlayerType::get_bot)
+            .any(|bot| !bot.get_engine().supports(players.len()));
+        [(unsupported, NewGameError::EngineNeedsTwoPlayers)]
+            .into_iter()
+            .filter_map(|(invalid, error)| invalid.then_some(error))
+            .collect()
+    }
+
+    /// A fresh game with the same settings and a new seed, optionally with the
+    /// player order rotated so someone else moves first
+    pub fn rematch(&self, rotate_players: bool) -> Game {
+        let mut game = self.clone();
+        game.clear();
+        game.seed = rand::random();
+        if rotate_players {
+            game.players.rotate_left(1);
+            game.player_profiles.rotate_left(1);
+            game.player_engines.rotate_left(1);
+        }
+        game
+    }
+
+    /// Plays a move in `column`, for games using [`Placement::Gravity`]
+    pub fn play_column(&mut self, column: isize) -> Result<(), super::PlayMoveError> {
+        self.play_move(Point::new(column, 0))
+    }
+}
+
+/// Why a game could not be created from its settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NewGameError {
+    WidthMustBeAtLeast2,
+    HeightMustBeAtLeast2,
+    /// Wider than [`MAX_BOARD_SIZE`]
+    WidthTooLarge,
+    /// Taller than [`MAX_BOARD_SIZE`]
+    HeightTooLarge,
+    GoalMustBeAtLeast2,
+    GoalMustBeLessThanWidth,
+    GoalMustBeLessThanHeight,
+    PlayersMustBeAtLeast2,
+    /// A bot uses an engine that cannot play this many players, see
+    /// [`Engine::supports`](super::Engine::supports)
+    EngineNeedsTwoPlayers,
+}
+
+impl Display for NewGameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WidthMustBeAtLeast2 => f.write_str("The width must be at least 2"),
+            Self::HeightMustBeAtLeast2 => f.write_str("The height must be at least 2"),
+            Self::WidthTooLarge => write!(f, "The width must be at most {MAX_BOARD_SIZE}"),
+            Self::HeightTooLarge => write!(f, "The height must be at most {MAX_BOARD_SIZE}"),
+            Self::GoalMustBeAtLeast2 => f.write_str("The goal must be at least 2"),
+            Self::GoalMustBeLessThanWidth => f.write_str("The goal must be less than the width"),
+            Self::GoalMustBeLessThanHeight => f.write_str("The goal must be less than the height"),
+            Self::PlayersMustBeAtLeast2 => f.write_str("There must be at least 2 players"),
+            Self::EngineNeedsTwoPlayers => {
+                f.write_str("Alpha-Beta bots can only play games of 2 players")
+            }
+        }
+    }
+}
+
+impl Error for NewGameError {}
+
+impl GameTrait for Game {
+    fn clear(&mut self) {
+        self.boards.iter_mut().for_each(BitBoard::clear);
+        self.occupied.clear();
+        self.history.clear();
+        self.redo_stack.clear();
+        self.hints.iter_mut().for_each(|hints| *hints = 0);
+        self.state = GameState::PlayerMove(0);
+    }
+
+    fn get_tile(&self, point: &Point) -> Result<Option<Player>, super::InvalidPointError> {
+        if point.x < 0 {
+            Err(super::InvalidPointError::XTooSmall)
+        } else if point.x >= self.width {
+            Err(super::InvalidPointError::XTooLarge)
+        } else if point.y < 0 {
+            Err(super::InvalidPointError::YTooSmall)
+        } else if point.y >= self.height {
+            Err(super::InvalidPointError::YTooLarge)
+        } else {
+            Ok(if self.occupied.get(point) {
+                self.boards.iter().position(|board| board.get(point))
+            } else {
+                None
+            })
+        }
+    }
+
+    fn get_board(&self) -> Vec<Vec<Option<Player>>> {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| self.get_tile(&Point::new(x, y)).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn get_placement(&self) -> Placement {
+        self.placement
+    }
+
+    fn get_landing_point(&self, point: &Point) -> Result<Point, super::PlayMoveError> {
+        match self.placement {
+            Placement::Free => match self.get_tile(point)? {
+                Some(tile_player) => Err(super::PlayMoveError::PointIsPopulated(tile_player)),
+                None => Ok(*point),
+            },
+            Placement::Gravity => {
+                self.get_tile(&Point::new(point.x, 0))?;
+                (0..self.height)
+                    .rev()
+                    .map(|y| Point::new(point.x, y))
+                    .find(|point| !self.occupied.get(point))
+                    .ok_or(super::PlayMoveError::ColumnIsFull(point.x))
+            }
+        }
+    }
+
+    fn get_valid_moves(&self) -> Vec<Point> {
+        match self.placement {
+            Placement::Free => (0..self.height)
+                .flat_map(|y| (0..self.width).map(move |x| Point::new(x, y)))
+                .filter(|point| !self.occupied.get(point))
+                .collect(),
+            Placement::Gravity => (0..self.width)
+                .filter_map(|x| self.get_landing_point(&Point::new(x, 0)).ok())
+                .collect(),
+        }
+    }
+
+    fn play_move(&mut self, point: Point) -> Result<(), super::PlayMoveError> {
+        match self.get_gamestate() {
+            GameState::PlayerMove(player) => {
+                let point = self.get_landing_point(&point)?;
+                self.place(point, player, None);
+                Ok(())
+            }
+            state => Err(super::PlayMoveError::InvalidGameState(state)),
+        }?;
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    fn get_history(&self) -> &[Move] {
+        &self.history
+    }
+
+    fn can_undo(&self) -> bool {
+        self.history.iter().any(|m| m.bot.is_none())
+    }
+
+    fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    fn undo(&mut self) -> bool {
+        // Rewind past the computer replies so it is the user's turn again
+        let Some(index) = self.history.iter().rposition(|m| m.bot.is_none()) else {
+            return false;
+        };
+        while self.history.len() > index {
+            let m = self.history.pop().unwrap();
+            self.boards[m.player].unset(&m.point);
+            self.occupied.unset(&m.point);
+            self.redo_stack.push(m);
+        }
+        self.state = self.scan_gamestate();
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(m) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.place(m.point, m.player, m.bot);
+        while let Some(m) = self.redo_stack.pop() {
+            if m.bot.is_none() {
+                self.redo_stack.push(m);
+                break;
+            }
+            self.place(m.point, m.player, m.bot);
+        }
+        true
+    }
+
+    fn get_gamestate(&self) -> GameState {
+        self.state.clone()
+    }
+
+    fn get_current_player(&self) -> Player {
+        self.history.len() % self.players.len()
+    }
+
+    fn get_width(&self) -> isize {
+        self.width
+    }
+
+    fn get_height(&self) -> isize {
+        self.height
+    }
+
+    fn get_goal(&self) -> isize {
+        self.goal
+    }
+
+    fn get_player_count(&self) -> usize {
+        self.players.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    use super::*;
+
+    /// The state of `game` worked out cell by cell, without the bitboards: the
+    /// last player to move who has a line of the goal wins with every such line
+    /// of theirs
+    fn brute_force_gamestate(game: &Game) -> GameState {
+        let tile = |point: Point| game.get_tile(&point).ok().flatten();
+        let mut lines = Vec::new();
+        for y in 0..game.height {
+            for x in 0..game.width {
+                let start = Point::new(x, y);
+                let Some(player) = tile(start) else {
+                    continue;
+                };
+                for direction in DIRECTIONS {
+                    if tile(start + direction * -1) == Some(player) {
+                        continue;
+                    }
+                    let length = (0..)
+                        .take_while(|&i| tile(start + direction * i) == Some(player))
+                        .count() as isize;
+                    if length >= game.goal {
+                        lines.push((
+                            player,
+                            Line {
+                                start,
+                                direction,
+                                length,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        let winner = game
+            .history
+            .iter()
+            .rev()
+            .map(|m| m.player)
+            .find(|&player| lines.iter().any(|&(owner, _)| owner == player));
+        match winner {
+            Some(player) => GameState::PlayerWon(Win {
+                player,
+                lines: lines
+                    .into_iter()
+                    .filter(|&(owner, _)| owner == player)
+                    .map(|(_, line)| line)
+                    .collect(),
+            }),
+            None if game.history.len() == (game.width * game.height) as usize => GameState::Draw,
+            None => GameState::PlayerMove(game.history.len() % game.players.len()),
+        }
+    }
+
+    /// Checks the state kept by `game` against [`brute_force_gamestate`], with
+    /// the winning lines in any order
+    fn assert_state(game: &Game) {
+        match (game.get_gamestate(), brute_force_gamestate(game)) {
+            (GameState::PlayerWon(actual), GameState::PlayerWon(expected)) => {
+                assert_eq!(actual.player, expected.player);
+                assert_eq!(
+                    actual.lines.iter().collect::<HashSet<_>>(),
+                    expected.lines.iter().collect::<HashSet<_>>()
+                );
+            }
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+    }
+
+    /// A random game on a random board, checked against a brute force scan
+    /// after every move, undo and redo
+    fn check_random_game(rng: &mut StdRng) {
+        let width = rng.gen_range(3..=9);
+        let height = rng.gen_range(3..=9);
+        let goal = rng.gen_range(2..width.min(height));
+        let players = vec![PlayerType::User; rng.gen_range(2..=4)];
+        let placement = *Placement::ALL.choose(rng).unwrap();
+        let mut game = Game::new(width, height, goal, players, placement).unwrap();
+
+        while let GameState::PlayerMove(_) = game.get_gamestate() {
+            let point = *game.get_valid_moves().choose(rng).unwrap();
+            game.play_move(point).unwrap();
+            assert_state(&game);
+
+            if rng.gen_bool(0.2) {
+                let undos = rng.gen_range(1..=3);
+                for _ in 0..undos {
+                    game.undo();
+                    assert_state(&game);
+                }
+                for _ in 0..rng.gen_range(0..=undos) {
+                    game.redo();
+                    assert_state(&game);
+                }
+            }
+        }
+
+        while game.undo() {
+            assert_state(&game);
+        }
+        while game.redo() {
+            assert_state(&game);
+        }
+    }
+
+    #[test]
+    fn incremental_state_matches_brute_force_scan() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..500 {
+            check_random_game(&mut rng);
+        }
+    }
+
+    #[test]
+    fn alpha_beta_bots_only_play_two_players() {
+        let bot = crate::Bot(
+            crate::Difficulty::Normal,
+            crate::Strategy::Neutral,
+            crate::Engine::AlphaBeta,
+        );
+        let new_game =
+            |players: Vec<PlayerType>| Game::new(7, 6, 4, players, Placement::Gravity).map(|_| ());
+        assert_eq!(new_game(vec![PlayerType::Computer(bot); 2]), Ok(()));
+        for players in [
+            vec![PlayerType::Computer(bot); 3],
+            vec![
+                PlayerType::User,
+                PlayerType::User,
+                PlayerType::External(bot),
+            ],
+        ] {
+            assert_eq!(new_game(players), Err(NewGameError::EngineNeedsTwoPlayers));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt::Display;
+
+use super::{
+    Bot, Difficulty, Engine, Game, GameState, GameTrait, Player, Point, Strategy, DIRECTIONS,
+};
+
+/// The bot whose move is recommended when someone asks for a hint. Games of
+/// more than two players use [`Engine::Paranoid`] in its place, since
+/// alpha-beta search only plays two.
+pub const HINT_BOT: Bot = Bot(Difficulty::Insane, Strategy::Neutral, Engine::AlphaBeta);
+
+/// A move recommended to the player whose turn it is
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Hint {
+    pub point: Point,
+    /// Why the move is good, when there is a simple way to say so
+    pub reason: Option<HintReason>,
+}
+
+/// The most important thing a recommended move does, checked in this order
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HintReason {
+    /// The move completes a line
+    Wins,
+    /// Another player would complete a line here on their turn
+    BlocksWin,
+    /// The move leaves more than one place to win next turn, so it can't be
+    /// stopped by blocking one of them
+    CreatesDoubleThreat,
+    /// Another player would leave themselves more than one place to win by
+    /// moving here
+    BlocksDoubleThreat,
+    /// The move leaves a place to win next turn
+    CreatesThreat,
+    /// The move caps one end of another player's line of this length, which
+    /// had space at both ends
+    BlocksOpenLine(isize),
+}
+
+impl Display for HintReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wins => f.write_str("Wins the game"),
+            Self::BlocksWin => f.write_str("Blocks an opponent's win"),
+            Self::CreatesDoubleThreat => f.write_str("Creates a double threat"),
+            Self::BlocksDoubleThreat => f.write_str("Stops an opponent's double threat"),
+            Self::CreatesThreat => f.write_str("Threatens to win next turn"),
+            Self::BlocksOpenLine(length) => match length {
+                2 => f.write_str("Blocks an opponent's open two"),
+                3 => f.write_str("Blocks an opponent's open three"),
+                4 => f.write_str("Blocks an opponent's open four"),
+                _ => write!(f, "Blocks an opponent's open line of {length}"),
+            },
+        }
+    }
+}
+
+impl Game {
+    /// The move [`HINT_BOT`] recommends for the player whose turn it is, or
+    /// `None` if the game is over
+    pub fn get_hint(&self) -> Option<Hint> {
+        let GameState::PlayerMove(player) = self.get_gamestate() else {
+            return None;
+        };
+        let bot = if HINT_BOT.get_engine().supports(self.get_player_count()) {
+            HINT_BOT
+        } else {
+            Bot(HINT_BOT.0, HINT_BOT.1, Engine::Paranoid)
+        };
+        let point = self.get_computer_move(bot)?;
+        Some(Hint {
+            point,
+            reason: self.explain_move(point, player),
+        })
+    }
+
+    /// Why `player` moving to `point` is a good idea, if it is for one of the
+    /// reasons in [`HintReason`]
+    pub fn explain_move(&self, point: Point, player: Player) -> Option<HintReason> {
+        let opponents: Vec<_> = (0..self.get_player_count())
+            .filter(|&other| other != player)
+            .collect();
+
+        if self.wins_at(point, player) {
+            Some(HintReason::Wins)
+        } else if opponents.iter().any(|&other| self.wins_at(point, other)) {
+            Some(HintReason::BlocksWin)
+        } else if self.count_threats_after(point, player) >= 2 {
+            Some(HintReason::CreatesDoubleThreat)
+        } else if opponents
+            .iter()
+            .any(|&other| self.count_threats_after(point, other) >= 2)
+        {
+            Some(HintReason::BlocksDoubleThreat)
+        } else if self.count_threats_after(point, player) == 1 {
+            Some(HintReason::CreatesThreat)
+        } else {
+            self.get_longest_open_line(point, player)
+                .map(HintReason::BlocksOpenLine)
+        }
+    }
+
+    /// How many moves would win for `player` straight after they moved to
+    /// `point`
+    fn count_threats_after(&self, point: Point, player: Player) -> usize {
+        let mut game = self.clone();
+        game.place(point, player, None);
+        game.get_valid_moves()
+            .into_iter()
+            .filter(|&threat| game.wins_at(threat, player))
+            .count()
+    }
+
+    /// The longest line of another player's pieces that `point` is at one end
+    /// of, with the other end empty. Lines too short to be worth blocking are
+    /// left out.
+    fn get_longest_open_line(&self, point: Point, player: Player) -> Option<isize> {
+        DIRECTIONS
+            .into_iter()
+            .flat_map(|direction| [direction * -1, direction])
+            .filter_map(|direction| {
+                let owner = self.get_tile(&(point + direction)).ok()??;
+                if owner == player {
+                    return None;
+                }
+                let length = (1..)
+                    .take_while(|&i| {
+                        matches!(self.get_tile(&(point + direction * i)), Ok(Some(tile)) if tile == owner)
+                    })
+                    .count() as isize;
+                let open = matches!(self.get_tile(&(point + direction * (length + 1))), Ok(None));
+                open.then_some(length)
+            })
+            .filter(|&length| length >= 2 && length >= self.get_goal() - 2)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Placement, PlayerType};
+
+    /// A two player game on an empty 9 by 9 board after the given moves, so the
+    /// first player is to move again
+    fn game_after(goal: isize, moves: &[(isize, isize)]) -> Game {
+        let mut game = Game::new(9, 9, goal, vec![PlayerType::User; 2], Placement::Free).unwrap();
+        for &(x, y) in moves {
+            game.play_move(Point::new(x, y)).unwrap();
+        }
+        game
+    }
+
+    fn explain(game: &Game, x: isize, y: isize) -> Option<HintReason> {
+        game.explain_move(Point::new(x, y), game.get_current_player())
+    }
+
+    #[test]
+    fn completing_a_line_wins() {
+        let game = game_after(4, &[(0, 0), (0, 8), (1, 0), (2, 8), (2, 0), (4, 8)]);
+        assert_eq!(explain(&game, 3, 0), Some(HintReason::Wins));
+    }
+
+    #[test]
+    fn taking_an_opponents_winning_cell_blocks_their_win() {
+        let game = game_after(4, &[(0, 0), (0, 8), (4, 4), (1, 8), (8, 0), (2, 8)]);
+        assert_eq!(explain(&game, 3, 8), Some(HintReason::BlocksWin));
+    }
+
+    #[test]
+    fn an_open_three_is_a_double_threat() {
+        let game = game_after(4, &[(1, 0), (8, 8), (2, 0), (6, 8)]);
+        assert_eq!(explain(&game, 3, 0), Some(HintReason::CreatesDoubleThreat));
+    }
+
+    #[test]
+    fn taking_the_cell_of_an_opponents_open_three_blocks_it() {
+        let game = game_after(4, &[(8, 8), (1, 0), (6, 8), (2, 0)]);
+        assert_eq!(explain(&game, 3, 0), Some(HintReason::BlocksDoubleThreat));
+    }
+
+    #[test]
+    fn a_three_against_the_edge_is_a_threat() {
+        let game = game_after(4, &[(0, 0), (8, 8), (1, 0), (6, 8)]);
+        assert_eq!(explain(&game, 2, 0), Some(HintReason::CreatesThreat));
+    }
+
+    #[test]
+    fn capping_an_opponents_open_three_blocks_it() {
+        let game = game_after(5, &[(6, 0), (2, 0), (8, 8), (3, 0), (6, 8), (4, 0)]);
+        assert_eq!(explain(&game, 5, 0), Some(HintReason::BlocksOpenLine(3)));
+    }
+
+    #[test]
+    fn a_quiet_move_has_no_reason() {
+        let game = game_after(4, &[(0, 0), (8, 8)]);
+        assert_eq!(explain(&game, 4, 4), None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Rules and computer players for "N in a row" games such as Four in a Row and
+//! gomoku, on boards of any size with any number of players.
+//!
+//! A [`Game`] is created from its settings and then driven through the
+//! [`GameTrait`] methods. Computer players are described by a [`Bot`], and
+//! [`Game::get_computer_move`] asks one for its move:
+//!
+//! ```
+//! use four_in_a_row_engine::{Bot, Game, GameState, GameTrait, Placement, PlayerType};
+//!
+//! let mut game = Game::new(
+//!     7,
+//!     6,
+//!     4,
+//!     vec![PlayerType::User, PlayerType::Computer(Bot::default())],
+//!     Placement::Gravity,
+//! )
+//! .unwrap();
+//!
+//! game.play_column(3).unwrap();
+//! if let Some(bot) = game.get_waiting_bot() {
+//!     let point = game.get_computer_move(bot).unwrap();
+//!     game.play_computer_move(point).unwrap();
+//! }
+//! assert_eq!(game.get_gamestate(), GameState::PlayerMove(0));
+//! ```
+//!
+//! Every source of randomness can be supplied by the caller through the
+//! `_with_rng` variants, such as [`Game::get_computer_move_with_rng`].
+
+mod bitboard;
+mod computer;
+mod external;
+mod game;
+mod hint;
+mod network;
+mod profile;
+mod protocol;
+mod rating;
+mod save;
+mod weights;
+
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, Mul},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+pub(crate) use bitboard::*;
+pub use computer::*;
+pub use external::*;
+pub use game::*;
+pub use hint::*;
+pub use network::*;
+pub use profile::*;
+pub use protocol::*;
+pub use rating::*;
+pub use save::*;
+pub use weights::*;
+
+/// A cell on the board, with `(0, 0)` in the top left corner and `y` growing
+/// downwards
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add<Point> for Point {
+    fn add(self, rhs: Point) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+
+    type Output = Point;
+}
+
+impl Mul<isize> for Point {
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+
+    type Output = Point;
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Index of a player, in the order they take their turns
+pub type Player = usize;
+
+/// The color each player's pieces are drawn in, as red, green and blue, in turn
+/// order. Players past the end of the table are drawn in white.
+pub const PLAYER_COLORS: [[u8; 3]; 13] = [
+    [255, 0, 0],     // Red
+    [0, 255, 0],     // Green
+    [0, 0, 255],     // Blue
+    [255, 255, 0],   // Yellow
+    [255, 0, 255],   // Magenta
+    [0, 255, 255],   // Aqua
+    [128, 0, 0],     // Maroon
+    [0, 128, 0],     // Dark green
+    [0, 0, 128],     // Navy
+    [0, 128, 128],   // Teal
+    [191, 128, 128], // Rose
+    [191, 128, 255], // Lavender
+    [153, 102, 51],  // Brown
+];
+
+/// A move in a game's history
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Move {
+    /// The cell the piece was placed in
+    pub point: Point,
+    pub player: Player,
+    /// The computer player that chose the move, if it was not chosen by a person
+    pub bot: Option<Bot>,
+}
+
+/// Where a piece may be placed on a player's turn
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Placement {
+    /// Any empty cell
+    #[default]
+    Free,
+    /// A column, with the piece dropping to the lowest empty cell in it
+    Gravity,
+}
+
+impl Placement {
+    pub const ALL: [Placement; 2] = [Self::Free, Self::Gravity];
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Free => "Free",
+            Self::Gravity => "Gravity",
+        })
+    }
+}
+
+impl FromStr for Placement {
+    type Err = ParseNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_name("placement", &Self::ALL, s)
+    }
+}
+
+/// A run of one player's pieces, `length` cells long from `start` in `direction`
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Line {
+    pub start: Point,
+    pub direction: Point,
+    pub length: isize,
+}
+
+impl Line {
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.length).map(|i| self.start + self.direction * i)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.points().any(|p| p.eq(point))
+    }
+}
+
+/// The winner of a game, with every line of theirs that reached the goal
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Win {
+    pub player: Player,
+    pub lines: Vec<Line>,
+}
+
+impl Win {
+    pub fn contains(&self, point: &Point) -> bool {
+        self.lines.iter().any(|line| line.contains(point))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum GameState {
+    PlayerMove(Player),
+    PlayerWon(Win),
+    Draw,
+}
+
+pub trait GameTrait {
+    /// Removes every piece from the board, starting the game over
+    fn clear(&mut self);
+    /// The player whose piece is at `point`, if any
+    fn get_tile(&self, point: &Point) -> Result<Option<Player>, InvalidPointError>;
+    /// Every tile on the board, indexed by row and then column
+    fn get_board(&self) -> Vec<Vec<Option<Player>>>;
+    fn get_placement(&self) -> Placement;
+    /// The cell a piece played at `point` would end up in. With
+    /// [`Placement::Gravity`] only the column of `point` matters.
+    fn get_landing_point(&self, point: &Point) -> Result<Point, PlayMoveError>;
+    /// The cells the current player may place a piece in
+    fn get_valid_moves(&self) -> Vec<Point>;
+    /// Places a piece for the current player, as chosen by a person
+    fn play_move(&mut self, point: Point) -> Result<(), PlayMoveError>;
+    /// Every move played so far, in order
+    fn get_history(&self) -> &[Move];
+    fn can_undo(&self) -> bool;
+    fn can_redo(&self) -> bool;
+    /// Takes back the last move made by a person, along with every computer
+    /// move played after it. Returns whether anything was undone.
+    fn undo(&mut self) -> bool;
+    /// Replays the last undone move made by a person, along with the computer
+    /// moves that followed it. Returns whether anything was redone.
+    fn redo(&mut self) -> bool;
+    fn get_gamestate(&self) -> GameState;
+    fn get_current_player(&self) -> Player;
+    fn get_width(&self) -> isize;
+    fn get_height(&self) -> isize;
+    fn get_goal(&self) -> isize;
+    fn get_player_count(&self) -> usize;
+}
+
+#[derive(Debug)]
+pub enum PlayMoveError {
+    InvalidPoint(InvalidPointError),
+    PointIsPopulated(Player),
+    ColumnIsFull(isize),
+    InvalidGameState(GameState),
+}
+
+#[derive(Debug)]
+pub enum InvalidPointError {
+    XTooSmall,
+    XTooLarge,
+    YTooSmall,
+    YTooLarge,
+}
+
+impl Display for PlayMoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPoint(error) => write!(f, "{error}"),
+            Self::PointIsPopulated(player) => {
+                write!(f, "That cell is already taken by player {}", player + 1)
+            }
+            Self::ColumnIsFull(column) => write!(f, "Column {} is full", column + 1),
+            Self::InvalidGameState(GameState::PlayerMove(player)) => {
+                write!(f, "It is player {}'s turn", player + 1)
+            }
+            Self::InvalidGameState(GameState::PlayerWon(win)) => {
+                write!(f, "The game is over, player {} won", win.player + 1)
+            }
+            Self::InvalidGameState(GameState::Draw) => {
+                write!(f, "The game is over, it ended in a draw")
+            }
+        }
+    }
+}
+
+impl Error for PlayMoveError {}
+
+impl Display for InvalidPointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::XTooSmall => "That point is left of the board",
+            Self::XTooLarge => "That point is right of the board",
+            Self::YTooSmall => "That point is above the board",
+            Self::YTooLarge => "That point is below the board",
+        })
+    }
+}
+
+impl Error for InvalidPointError {}
+
+/// A name that does not match any of the values it could be
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNameError {
+    /// What was being parsed, such as "difficulty"
+    pub kind: &'static str,
+    pub name: String,
+    pub expected: Vec<String>,
+}
+
+impl Display for ParseNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unknown {} \"{}\", expected one of: {}",
+            self.kind,
+            self.name,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl Error for ParseNameError {}
+
+/// Finds the value in `all` whose displayed name matches `name`, ignoring case,
+/// spaces, hyphens and underscores, so "alpha-beta" matches "Alpha-Beta"
+pub(crate) fn parse_name<T: Copy + Display>(
+    kind: &'static str,
+    all: &[T],
+    name: &str,
+) -> Result<T, ParseNameError> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    all.iter()
+        .find(|value| normalize(&value.to_string()) == normalize(name))
+        .copied()
+        .ok_or_else(|| ParseNameError {
+            kind,
+            name: name.to_string(),
+            expected: all.iter().map(ToString::to_string).collect(),
+        })
+}
+
+impl From<InvalidPointError> for PlayMoveError {
+    fn from(value: InvalidPointError) -> Self {
+        PlayMoveError::InvalidPoint(value)
+    }
+}
@@ -0,0 +1,747 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Game, GameState, GameTrait, LoadError, Player, PlayerType, Point, SaveFile};
+
+/// The port games are hosted on unless another one is chosen
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// How long joining waits for the host before giving up
+pub const JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A line sent by a joining player to the host. Every message in either
+/// direction is a line of JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Asks for a remote seat: the given one, such as the seat held before
+    /// reconnecting, or any free one
+    Join { seat: Option<Player> },
+    /// Asks to follow the game without playing it
+    Spectate,
+    /// Plays a move for the joined seat
+    Move(Point),
+}
+
+/// A line sent by the host to a joined player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HostMessage {
+    /// The seat the player was given, sent on joining and whenever a rematch
+    /// moves the seats around
+    Welcome { seat: Player },
+    /// The whole game, sent on joining and whenever it changes
+    Sync(SaveFile),
+    /// Whether each seat has someone playing it right now
+    Present(Vec<bool>),
+    /// How many spectators are following the game, sent whenever it changes
+    Spectators(usize),
+    /// Why the last move was not played
+    Rejected(String),
+    /// Why the host is ending the connection
+    Closed(String),
+}
+
+/// Why joining or talking to a host failed
+#[derive(Debug)]
+pub enum NetworkError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    /// The host turned the player away
+    Refused(String),
+    /// The host sent a game that does not hold up
+    InvalidGame(LoadError),
+    /// The host sent something other than what joining expects
+    Unexpected(Box<HostMessage>),
+    Disconnected,
+}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not reach the host: {error}"),
+            Self::Parse(error) => write!(f, "The host sent something unreadable: {error}"),
+            Self::Refused(reason) => write!(f, "The host refused: {reason}"),
+            Self::InvalidGame(error) => write!(f, "The host sent an invalid game: {error}"),
+            Self::Unexpected(message) => write!(f, "The host unexpectedly sent {message:?}"),
+            Self::Disconnected => f.write_str("The host closed the connection"),
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+/// Messages arriving on background threads, handed out one at a time. Clones
+/// share the same queue.
+#[derive(Debug)]
+pub struct EventQueue<T>(Arc<Mutex<Receiver<T>>>);
+
+impl<T> Clone for EventQueue<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> EventQueue<T> {
+    /// Waits for the next message, or `None` once no more can arrive
+    pub fn next(&self) -> Option<T> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recv()
+            .ok()
+    }
+}
+
+/// Something that happened on one of a host's connections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostEvent {
+    Received(usize, ClientMessage),
+    /// The connection closed or sent something unreadable
+    Disconnected(usize),
+}
+
+/// What a [`HostEvent`] changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostUpdate {
+    None,
+    Joined(Player),
+    Left(Player),
+    /// A remote player moved
+    Moved(Player),
+    /// A spectator came or went, leaving this many
+    Spectators(usize),
+}
+
+/// Serves a game to players on other machines, who each play one of its
+/// [`PlayerType::Remote`] seats. Every move they send is checked by playing it
+/// with [`GameTrait::play_move`], and the whole game is sent back out whenever
+/// it changes, so a player who reconnects picks up where the game is. Any
+/// number of spectators can follow along, receiving the same updates without
+/// being able to move.
+pub struct GameHost {
+    port: u16,
+    events: EventQueue<HostEvent>,
+    streams: Arc<Mutex<HashMap<usize, TcpStream>>>,
+    /// The connection playing each seat, if any
+    seats: Vec<Option<usize>>,
+    /// The connections following the game without a seat
+    spectators: Vec<usize>,
+    closed: Arc<AtomicBool>,
+    /// The game as last sent, so an unchanged game is not sent again
+    last_sync: String,
+}
+
+impl GameHost {
+    /// Listens for players on `port` on every network interface, or on any
+    /// free port for port 0
+    pub fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let port = listener.local_addr()?.port();
+        let (sender, receiver) = mpsc::channel();
+        let streams = Arc::new(Mutex::new(HashMap::new()));
+        let closed = Arc::new(AtomicBool::new(false));
+
+        {
+            let streams = streams.clone();
+            let closed = closed.clone();
+            thread::spawn(move || accept(listener, sender, streams, closed));
+        }
+
+        Ok(Self {
+            port,
+            events: EventQueue(Arc::new(Mutex::new(receiver))),
+            streams,
+            seats: Vec::new(),
+            spectators: Vec::new(),
+            closed,
+            last_sync: String::new(),
+        })
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn events(&self) -> EventQueue<HostEvent> {
+        self.events.clone()
+    }
+
+    pub fn get_spectator_count(&self) -> usize {
+        self.spectators.len()
+    }
+
+    /// Whether each seat of `game` has someone playing it: every local seat,
+    /// and the remote seats with a player connected
+    pub fn get_present(&self, game: &Game) -> Vec<bool> {
+        game.get_players()
+            .iter()
+            .enumerate()
+            .map(|(seat, player)| {
+                *player != PlayerType::Remote || self.seats.get(seat).is_some_and(Option::is_some)
+            })
+            .collect()
+    }
+
+    /// Acts on an event from one of the connections, playing any move it
+    /// carries in `game`
+    pub fn handle(&mut self, event: HostEvent, game: &mut Game) -> HostUpdate {
+        self.seats.resize(game.get_player_count(), None);
+        match event {
+            HostEvent::Received(connection, ClientMessage::Join { seat }) => {
+                self.join(connection, seat, game)
+            }
+            HostEvent::Received(connection, ClientMessage::Spectate) => {
+                self.spectate(connection, game)
+            }
+            HostEvent::Received(connection, ClientMessage::Move(_))
+                if self.spectators.contains(&connection) =>
+            {
+                let reason = String::from("Spectators can't move");
+                self.send(connection, &HostMessage::Rejected(reason));
+                HostUpdate::None
+            }
+            HostEvent::Received(connection, ClientMessage::Move(point)) => {
+                let Some(seat) = self.get_seat(connection) else {
+                    self.send(
+                        connection,
+                        &HostMessage::Rejected(String::from("Join a seat first")),
+                    );
+                    return HostUpdate::None;
+                };
+                if game.get_gamestate() != GameState::PlayerMove(seat) {
+                    let reason = String::from("It is not your turn");
+                    self.send(connection, &HostMessage::Rejected(reason));
+                    return HostUpdate::None;
+                }
+                match game.play_move(point) {
+                    Ok(()) => {
+                        self.sync(game);
+                        HostUpdate::Moved(seat)
+                    }
+                    Err(error) => {
+                        self.send(connection, &HostMessage::Rejected(error.to_string()));
+                        HostUpdate::None
+                    }
+                }
+            }
+            HostEvent::Disconnected(connection) => {
+                self.streams
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .remove(&connection);
+                if let Some(index) = self.spectators.iter().position(|&c| c == connection) {
+                    self.spectators.remove(index);
+                    self.broadcast(&HostMessage::Spectators(self.spectators.len()));
+                    return HostUpdate::Spectators(self.spectators.len());
+                }
+                match self.get_seat(connection) {
+                    Some(seat) => {
+                        self.seats[seat] = None;
+                        self.broadcast(&HostMessage::Present(self.get_present(game)));
+                        HostUpdate::Left(seat)
+                    }
+                    None => HostUpdate::None,
+                }
+            }
+        }
+    }
+
+    fn join(&mut self, connection: usize, seat: Option<Player>, game: &Game) -> HostUpdate {
+        let free = |seat: &Player| {
+            game.get_players().get(*seat) == Some(&PlayerType::Remote)
+                && self.seats[*seat].is_none()
+        };
+        let seat = match seat {
+            _ if self.is_joined(connection) => Err(String::from("Already joined")),
+            Some(seat) if free(&seat) => Ok(seat),
+            Some(seat) => Err(format!("Seat {} is not free to join", seat + 1)),
+            None => (0..game.get_player_count())
+                .find(free)
+                .ok_or_else(|| String::from("There are no free seats")),
+        };
+
+        match seat {
+            Ok(seat) => {
+                self.seats[seat] = Some(connection);
+                self.send(connection, &HostMessage::Welcome { seat });
+                self.send(connection, &HostMessage::Sync(SaveFile::from(game)));
+                self.send(connection, &HostMessage::Spectators(self.spectators.len()));
+                self.broadcast(&HostMessage::Present(self.get_present(game)));
+                HostUpdate::Joined(seat)
+            }
+            Err(reason) => {
+                self.send(connection, &HostMessage::Closed(reason));
+                self.disconnect(connection);
+                HostUpdate::None
+            }
+        }
+    }
+
+    /// Sends a new spectator the whole game, and everyone the new count
+    fn spectate(&mut self, connection: usize, game: &Game) -> HostUpdate {
+        if self.is_joined(connection) {
+            self.send(
+                connection,
+                &HostMessage::Closed(String::from("Already joined")),
+            );
+            self.disconnect(connection);
+            return HostUpdate::None;
+        }
+        self.spectators.push(connection);
+        self.send(connection, &HostMessage::Sync(SaveFile::from(game)));
+        self.send(connection, &HostMessage::Present(self.get_present(game)));
+        self.broadcast(&HostMessage::Spectators(self.spectators.len()));
+        HostUpdate::Spectators(self.spectators.len())
+    }
+
+    /// Whether the connection already has a seat or is spectating
+    fn is_joined(&self, connection: usize) -> bool {
+        self.get_seat(connection).is_some() || self.spectators.contains(&connection)
+    }
+
+    /// Sends `game` to every player if it has changed since it was last sent
+    pub fn sync(&mut self, game: &Game) {
+        let message = HostMessage::Sync(SaveFile::from(game));
+        let Ok(line) = serde_json::to_string(&message) else {
+            return;
+        };
+        if line != self.last_sync {
+            self.broadcast_line(&line);
+            self.last_sync = line;
+        }
+    }
+
+    /// Moves every player along with their seat after a rematch rotated the
+    /// seats of `game`, which has already been rotated
+    pub fn rotate_seats(&mut self, game: &Game) {
+        self.seats.resize(game.get_player_count(), None);
+        self.seats.rotate_left(1);
+        for (seat, connection) in self.seats.clone().into_iter().enumerate() {
+            if let Some(connection) = connection {
+                self.send(connection, &HostMessage::Welcome { seat });
+            }
+        }
+        self.broadcast(&HostMessage::Present(self.get_present(game)));
+        self.sync(game);
+    }
+
+    fn get_seat(&self, connection: usize) -> Option<Player> {
+        self.seats.iter().position(|&seat| seat == Some(connection))
+    }
+
+    fn send(&self, connection: usize, message: &HostMessage) {
+        let streams = self.streams.lock().unwrap_or_else(PoisonError::into_inner);
+        if let (Some(stream), Ok(line)) = (streams.get(&connection), serde_json::to_string(message))
+        {
+            write_line(stream, &line);
+        }
+    }
+
+    fn broadcast(&self, message: &HostMessage) {
+        if let Ok(line) = serde_json::to_string(message) {
+            self.broadcast_line(&line);
+        }
+    }
+
+    /// Sends `line` to every player with a seat and every spectator
+    fn broadcast_line(&self, line: &str) {
+        let streams = self.streams.lock().unwrap_or_else(PoisonError::into_inner);
+        for connection in self.seats.iter().flatten().chain(&self.spectators) {
+            if let Some(stream) = streams.get(connection) {
+                write_line(stream, line);
+            }
+        }
+    }
+
+    /// Ends a connection, which its reader reports as a disconnection
+    fn disconnect(&self, connection: usize) {
+        let streams = self.streams.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(stream) = streams.get(&connection) {
+            stream.shutdown(Shutdown::Both).ok();
+        }
+    }
+}
+
+impl Drop for GameHost {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+        if let Ok(line) =
+            serde_json::to_string(&HostMessage::Closed(String::from("The host left the game")))
+        {
+            self.broadcast_line(&line);
+        }
+        for stream in self
+            .streams
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+        {
+            stream.shutdown(Shutdown::Both).ok();
+        }
+        // Wakes the listener so it sees the host is closed
+        TcpStream::connect(("127.0.0.1", self.port)).ok();
+    }
+}
+
+/// Takes connections until the host closes, reading each on its own thread
+fn accept(
+    listener: TcpListener,
+    sender: Sender<HostEvent>,
+    streams: Arc<Mutex<HashMap<usize, TcpStream>>>,
+    closed: Arc<AtomicBool>,
+) {
+    for (connection, stream) in listener.incoming().enumerate() {
+        if closed.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        streams
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(connection, stream);
+
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(message) = line
+                    .map_err(|_| ())
+                    .and_then(|line| serde_json::from_str(&line).map_err(|_| ()))
+                else {
+                    break;
+                };
+                if sender
+                    .send(HostEvent::Received(connection, message))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            sender.send(HostEvent::Disconnected(connection)).ok();
+        });
+    }
+}
+
+/// Sends a line, leaving a failed connection for its reader to notice
+fn write_line(mut stream: &TcpStream, line: &str) {
+    if writeln!(stream, "{line}").is_err() {
+        stream.shutdown(Shutdown::Both).ok();
+    }
+}
+
+/// A seat in a game hosted by another instance
+pub struct GameClient {
+    address: SocketAddr,
+    stream: TcpStream,
+    events: EventQueue<HostMessage>,
+}
+
+impl GameClient {
+    /// Connects to the host at `address`, such as `192.168.1.2:7878`, and takes
+    /// `seat`, or any free seat for `None`. Returns the seat taken and the game
+    /// as it stands.
+    pub fn join(
+        address: impl ToSocketAddrs,
+        seat: Option<Player>,
+    ) -> Result<(Self, Player, Game), NetworkError> {
+        let (address, stream, mut reader) = connect(address, &ClientMessage::Join { seat })?;
+        let seat = match read_message(&mut reader)? {
+            HostMessage::Welcome { seat } => seat,
+            HostMessage::Closed(reason) => return Err(NetworkError::Refused(reason)),
+            message => return Err(NetworkError::Unexpected(Box::new(message))),
+        };
+        let game = read_game(&mut reader)?;
+        Ok((Self::start(address, stream, reader)?, seat, game))
+    }
+
+    /// Connects to the host at `address` to follow its game without playing.
+    /// Returns the game as it stands, with every move played so far.
+    pub fn spectate(address: impl ToSocketAddrs) -> Result<(Self, Game), NetworkError> {
+        let (address, stream, mut reader) = connect(address, &ClientMessage::Spectate)?;
+        let game = read_game(&mut reader)?;
+        Ok((Self::start(address, stream, reader)?, game))
+    }
+
+    /// Hands the rest of the host's messages to a thread, once joining is done
+    fn start(
+        address: SocketAddr,
+        stream: TcpStream,
+        mut reader: BufReader<TcpStream>,
+    ) -> Result<Self, NetworkError> {
+        stream.set_read_timeout(None).map_err(NetworkError::Io)?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(message) = read_message(&mut reader) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            address,
+            stream,
+            events: EventQueue(Arc::new(Mutex::new(receiver))),
+        })
+    }
+
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Messages from the host, ending when the connection does
+    pub fn events(&self) -> EventQueue<HostMessage> {
+        self.events.clone()
+    }
+
+    /// Asks the host to play a move for this player's seat. The host answers
+    /// with the updated game, or with why the move was rejected.
+    pub fn send_move(&self, point: Point) -> Result<(), NetworkError> {
+        let line =
+            serde_json::to_string(&ClientMessage::Move(point)).map_err(NetworkError::Parse)?;
+        writeln!(&self.stream, "{line}").map_err(NetworkError::Io)
+    }
+}
+
+impl Drop for GameClient {
+    fn drop(&mut self) {
+        self.stream.shutdown(Shutdown::Both).ok();
+    }
+}
+
+/// Opens a connection to the host and sends the first message, with reads
+/// timing out until joining is done
+fn connect(
+    address: impl ToSocketAddrs,
+    message: &ClientMessage,
+) -> Result<(SocketAddr, TcpStream, BufReader<TcpStream>), NetworkError> {
+    let address = address
+        .to_socket_addrs()
+        .map_err(NetworkError::Io)?
+        .next()
+        .ok_or_else(|| {
+            NetworkError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "the address has no host",
+            ))
+        })?;
+    let stream = TcpStream::connect_timeout(&address, JOIN_TIMEOUT).map_err(NetworkError::Io)?;
+    stream
+        .set_read_timeout(Some(JOIN_TIMEOUT))
+        .map_err(NetworkError::Io)?;
+    let reader = BufReader::new(stream.try_clone().map_err(NetworkError::Io)?);
+
+    let line = serde_json::to_string(message).map_err(NetworkError::Parse)?;
+    writeln!(&stream, "{line}").map_err(NetworkError::Io)?;
+    Ok((address, stream, reader))
+}
+
+/// Reads the game the host sends on joining
+fn read_game(reader: &mut impl BufRead) -> Result<Game, NetworkError> {
+    match read_message(reader)? {
+        HostMessage::Sync(save) => Game::try_from(save).map_err(NetworkError::InvalidGame),
+        HostMessage::Closed(reason) => Err(NetworkError::Refused(reason)),
+        message => Err(NetworkError::Unexpected(Box::new(message))),
+    }
+}
+
+fn read_message(reader: &mut impl BufRead) -> Result<HostMessage, NetworkError> {
+    let mut line = String::new();
+    match reader.read_line(&mut line).map_err(NetworkError::Io)? {
+        0 => Err(NetworkError::Disconnected),
+        _ => serde_json::from_str(&line).map_err(NetworkError::Parse),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Placement;
+
+    /// Joins the host from another thread while the host handles the join
+    fn join(
+        host: &mut GameHost,
+        game: &mut Game,
+        seat: Option<Player>,
+    ) -> (GameClient, Player, Game) {
+        let port = host.get_port();
+        let joining = thread::spawn(move || GameClient::join(("127.0.0.1", port), seat));
+        let event = host.events().next().unwrap();
+        let update = host.handle(event, game);
+        let (client, seat, joined) = joining.join().unwrap().unwrap();
+        assert_eq!(update, HostUpdate::Joined(seat));
+        (client, seat, joined)
+    }
+
+    /// Starts spectating from another thread while the host handles it
+    fn spectate(host: &mut GameHost, game: &mut Game) -> (GameClient, Game) {
+        let port = host.get_port();
+        let joining = thread::spawn(move || GameClient::spectate(("127.0.0.1", port)));
+        let event = host.events().next().unwrap();
+        let update = host.handle(event, game);
+        let (client, watched) = joining.join().unwrap().unwrap();
+        assert_eq!(update, HostUpdate::Spectators(host.get_spectator_count()));
+        (client, watched)
+    }
+
+    /// A game between a person at the host and a remote player, with a move
+    /// already played
+    fn hosted_game() -> Game {
+        let mut game = Game::new(
+            7,
+            6,
+            4,
+            vec![PlayerType::User, PlayerType::Remote],
+            Placement::Gravity,
+        )
+        .unwrap();
+        game.play_column(3).unwrap();
+        game
+    }
+
+    /// Skips messages until one matches
+    fn find(client: &GameClient, matches: impl Fn(&HostMessage) -> bool) -> HostMessage {
+        let events = client.events();
+        loop {
+            let message = events.next().unwrap();
+            if matches(&message) {
+                return message;
+            }
+        }
+    }
+
+    #[test]
+    fn remote_players_move_in_turn_and_rejoin_their_seat() {
+        let mut game = Game::new(
+            7,
+            6,
+            4,
+            vec![PlayerType::Remote, PlayerType::Remote],
+            Placement::Gravity,
+        )
+        .unwrap();
+        let mut host = GameHost::start(0).unwrap();
+        let events = host.events();
+
+        let (first, first_seat, _) = join(&mut host, &mut game, None);
+        let (second, second_seat, _) = join(&mut host, &mut game, None);
+        assert_eq!((first_seat, second_seat), (0, 1));
+
+        second.send_move(Point::new(3, 0)).unwrap();
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::None);
+        let message = find(&second, |message| {
+            matches!(message, HostMessage::Rejected(_))
+        });
+        assert!(
+            matches!(message, HostMessage::Rejected(reason) if reason == "It is not your turn")
+        );
+        assert!(game.get_history().is_empty());
+
+        first.send_move(Point::new(3, 0)).unwrap();
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::Moved(0));
+        let message = find(&second, |message| matches!(message, HostMessage::Sync(_)));
+        let HostMessage::Sync(save) = message else {
+            unreachable!()
+        };
+        assert_eq!(save.moves, game.get_history());
+
+        drop(second);
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::Left(1));
+        assert_eq!(host.get_present(&game), [true, false]);
+
+        let (_second, seat, rejoined) = join(&mut host, &mut game, Some(1));
+        assert_eq!(seat, 1);
+        assert_eq!(rejoined.get_history(), game.get_history());
+        assert_eq!(rejoined.get_gamestate(), GameState::PlayerMove(1));
+        assert_eq!(host.get_present(&game), [true, true]);
+    }
+
+    #[test]
+    fn spectators_join_mid_game_and_follow_it() {
+        let mut game = hosted_game();
+        let mut host = GameHost::start(0).unwrap();
+        let events = host.events();
+        let (player, _, _) = join(&mut host, &mut game, None);
+
+        let (spectator, watched) = spectate(&mut host, &mut game);
+        assert_eq!(watched.get_history(), game.get_history());
+        assert_eq!(host.get_spectator_count(), 1);
+
+        player.send_move(Point::new(4, 0)).unwrap();
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::Moved(1));
+        let message = find(&spectator, |message| {
+            matches!(message, HostMessage::Sync(_))
+        });
+        let HostMessage::Sync(save) = message else {
+            unreachable!()
+        };
+        assert_eq!(save.moves.len(), 2);
+        assert_eq!(save.moves, game.get_history());
+    }
+
+    #[test]
+    fn spectators_cannot_move() {
+        let mut game = hosted_game();
+        let mut host = GameHost::start(0).unwrap();
+        let events = host.events();
+        let (_player, _, _) = join(&mut host, &mut game, None);
+        let (spectator, _) = spectate(&mut host, &mut game);
+
+        spectator.send_move(Point::new(4, 0)).unwrap();
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::None);
+        let message = find(&spectator, |message| {
+            matches!(message, HostMessage::Rejected(_))
+        });
+        assert!(
+            matches!(message, HostMessage::Rejected(reason) if reason == "Spectators can't move")
+        );
+        assert_eq!(game.get_history().len(), 1);
+    }
+
+    #[test]
+    fn players_are_told_how_many_are_watching() {
+        let mut game = hosted_game();
+        let mut host = GameHost::start(0).unwrap();
+        let events = host.events();
+        let (player, _, _) = join(&mut host, &mut game, None);
+        let next_count = || match find(&player, |message| {
+            matches!(message, HostMessage::Spectators(_))
+        }) {
+            HostMessage::Spectators(count) => count,
+            _ => unreachable!(),
+        };
+        assert_eq!(next_count(), 0);
+
+        let (first, _) = spectate(&mut host, &mut game);
+        let (_second, _) = spectate(&mut host, &mut game);
+        assert_eq!(next_count(), 1);
+        assert_eq!(next_count(), 2);
+
+        drop(first);
+        let update = host.handle(events.next().unwrap(), &mut game);
+        assert_eq!(update, HostUpdate::Spectators(1));
+        assert_eq!(next_count(), 1);
+    }
+}
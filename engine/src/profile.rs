@@ -0,0 +1,221 @@
+use std::{collections::HashSet, error::Error, fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::ComputerWeights;
+
+/// Deepest search a profile may ask for, beyond which moves take too long
+pub const MAX_PROFILE_SEARCH_DEPTH: usize = 8;
+
+/// Widest search a profile may ask for
+pub const MAX_PROFILE_SEARCH_BREADTH: usize = 20;
+
+/// A user defined bot: its own weights, and optionally its own search limits in
+/// place of those of its difficulty
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Trimmed as it is read, so names that only differ in the spaces around
+    /// them are the same name
+    #[serde(deserialize_with = "deserialize_name")]
+    pub name: String,
+    pub weights: ComputerWeights,
+    /// How many moves ahead the search engines look
+    #[serde(default)]
+    pub search_depth: Option<usize>,
+    /// How many of the most promising moves the search engines expand
+    #[serde(default)]
+    pub search_breadth: Option<usize>,
+}
+
+fn deserialize_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    String::deserialize(deserializer).map(|name| name.trim().to_string())
+}
+
+/// Every profile from a profiles file
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profiles {
+    profiles: Vec<Profile>,
+}
+
+/// A problem with one of the profiles in a file
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ProfileError {
+    /// The profile at this position in the file has no name
+    EmptyName(usize),
+    DuplicateName(String),
+    AllWeightsZero(String),
+    SearchDepthOutOfRange(String, usize),
+    SearchBreadthOutOfRange(String, usize),
+}
+
+/// Why a profiles file could not be used
+#[derive(Debug)]
+pub enum ProfilesError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    Invalid(Vec<ProfileError>),
+}
+
+impl Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyName(index) => write!(f, "Profile {} has no name", index + 1),
+            Self::DuplicateName(name) => write!(f, "There is more than one profile named {name}"),
+            Self::AllWeightsZero(name) => {
+                write!(f, "Profile {name} needs at least one weight above 0")
+            }
+            Self::SearchDepthOutOfRange(name, depth) => write!(
+                f,
+                "Profile {name} has a search depth of {depth}, it must be from 1 to {MAX_PROFILE_SEARCH_DEPTH}"
+            ),
+            Self::SearchBreadthOutOfRange(name, breadth) => write!(
+                f,
+                "Profile {name} has a search breadth of {breadth}, it must be from 1 to {MAX_PROFILE_SEARCH_BREADTH}"
+            ),
+        }
+    }
+}
+
+impl Error for ProfileError {}
+
+impl Display for ProfilesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read the profiles file: {error}"),
+            Self::Format(error) => write!(f, "The profiles file is malformed: {error}"),
+            Self::Invalid(errors) => {
+                let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "The profiles file is invalid: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl Error for ProfilesError {}
+
+impl Profile {
+    /// Every problem with the profile other than its name clashing with another
+    pub fn validate(&self, index: usize) -> Vec<ProfileError> {
+        let weights = &self.weights;
+        let all_zero = [
+            weights.computer,
+            weights.opponent,
+            weights.empty,
+            weights.populated,
+            weights.streak_computer,
+            weights.streak_opponent,
+        ]
+        .iter()
+        .all(|&weight| weight == 0);
+
+        // Unnamed profiles are referred to by their position in the file
+        let label = if self.name.is_empty() {
+            (index + 1).to_string()
+        } else {
+            self.name.clone()
+        };
+
+        let mut errors = Vec::new();
+        if self.name.is_empty() {
+            errors.push(ProfileError::EmptyName(index));
+        }
+        if all_zero {
+            errors.push(ProfileError::AllWeightsZero(label.clone()));
+        }
+        if let Some(depth) = self
+            .search_depth
+            .filter(|depth| !(1..=MAX_PROFILE_SEARCH_DEPTH).contains(depth))
+        {
+            errors.push(ProfileError::SearchDepthOutOfRange(label.clone(), depth));
+        }
+        if let Some(breadth) = self
+            .search_breadth
+            .filter(|breadth| !(1..=MAX_PROFILE_SEARCH_BREADTH).contains(breadth))
+        {
+            errors.push(ProfileError::SearchBreadthOutOfRange(label, breadth));
+        }
+        errors
+    }
+}
+
+impl Profiles {
+    /// Reads and checks the profiles at `path`. There are no profiles if the
+    /// file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProfilesError> {
+        let profiles: Self = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(ProfilesError::Format)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(ProfilesError::Io(error)),
+        };
+
+        let errors = profiles.validate();
+        if errors.is_empty() {
+            Ok(profiles)
+        } else {
+            Err(ProfilesError::Invalid(errors))
+        }
+    }
+
+    /// Every problem with every profile, in file order
+    pub fn validate(&self) -> Vec<ProfileError> {
+        let mut names = HashSet::new();
+        let mut errors = Vec::new();
+        for (index, profile) in self.profiles.iter().enumerate() {
+            errors.extend(profile.validate(index));
+            if !profile.name.is_empty() && !names.insert(&profile.name) {
+                errors.push(ProfileError::DuplicateName(profile.name.clone()));
+            }
+        }
+        errors
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        let name = name.trim();
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn get_names(&self) -> Vec<String> {
+        self.profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Profiles with the given names, read from JSON as a file would be
+    fn profiles(names: &[&str]) -> Profiles {
+        let weights = serde_json::to_value(ComputerWeights::built_in(
+            crate::Difficulty::Normal,
+            crate::Strategy::Neutral,
+        ))
+        .unwrap();
+        let profiles: Vec<_> = names
+            .iter()
+            .map(|name| serde_json::json!({ "name": name, "weights": weights }))
+            .collect();
+        serde_json::from_value(serde_json::json!({ "profiles": profiles })).unwrap()
+    }
+
+    #[test]
+    fn names_are_trimmed_when_read() {
+        let profiles = profiles(&[" Ann "]);
+        assert_eq!(profiles.get_names(), ["Ann"]);
+        assert!(profiles.get("Ann").is_some());
+        assert!(profiles.get(" Ann").is_some());
+    }
+
+    #[test]
+    fn names_that_differ_in_spaces_are_duplicates() {
+        assert_eq!(
+            profiles(&["Ann", "Ann "]).validate(),
+            [ProfileError::DuplicateName(String::from("Ann"))]
+        );
+        assert_eq!(profiles(&[" "]).validate(), [ProfileError::EmptyName(0)]);
+    }
+}
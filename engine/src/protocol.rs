@@ -0,0 +1,695 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{
+    Bot, Budget, Engine, Game, GameState, GameTrait, NewGameError, Placement, Player, PlayerType,
+    Point, Profile, WeightTable, Win, MAX_PROFILE_SEARCH_DEPTH,
+};
+
+/// Version of the protocol, answered to a `protocol` request
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Most players a `newgame` can have, since a `board` writes each player as one
+/// base 36 digit
+pub const MAX_PROTOCOL_PLAYERS: usize = 36;
+
+/// Settings of a `newgame` request
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct NewGameOptions {
+    pub width: isize,
+    pub height: isize,
+    pub goal: isize,
+    pub players: usize,
+    pub placement: Placement,
+    pub seed: Option<u64>,
+}
+
+impl Default for NewGameOptions {
+    fn default() -> Self {
+        Self {
+            width: 7,
+            height: 6,
+            goal: 4,
+            players: 2,
+            placement: Placement::Gravity,
+            seed: None,
+        }
+    }
+}
+
+/// How long a `go` request may search for
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct SearchLimits {
+    pub bot: Bot,
+    /// Moves ahead to search, in place of the bot's difficulty
+    pub depth: Option<usize>,
+    pub time: Option<Duration>,
+}
+
+/// A line sent to the engine, in a protocol for driving a game and its bots
+/// from another process.
+///
+/// The client writes one request per line, and the engine answers every
+/// request with exactly one line. Words are separated by spaces, and options
+/// are written as `name=value` in any order. Cells are numbered from 0, with
+/// `x` counting columns from the left and `y` rows from the top.
+///
+/// | Request | Response |
+/// | --- | --- |
+/// | `protocol` | `protocol 1`, the version of the protocol spoken |
+/// | `newgame [width=7] [height=6] [goal=4] [players=2] [placement=gravity] [seed=N]` | `ok` |
+/// | `move X [Y]` plays for the player to move. `Y` is only needed without gravity. | `ok` |
+/// | `undo` takes back the last move | `ok` |
+/// | `state` | `state move P`, `state won P` or `state draw` |
+/// | `board` | `board ROW/ROW/…`, from the top row down, with `.` for an empty cell and the player's number in base 36 otherwise |
+/// | `go [bot=normal:neutral:heuristic] [depth=N] [time=MS]` asks the bot for the move of the player to move | `bestmove X Y`, or `bestmove none` when there are no moves |
+/// | `quit` | `ok`, then the engine exits |
+///
+/// Any request can instead be answered with `error MESSAGE` when it is not
+/// understood or can't be carried out, which leaves the game as it was. Games
+/// have at most [`MAX_PROTOCOL_PLAYERS`] players.
+///
+/// A `go` with a `depth` searches that many moves ahead in place of the bot's
+/// difficulty. A `go` with a `time` limit searches deeper and deeper until the
+/// next depth looks unlikely to finish in time, or for the Monte Carlo engine
+/// plays out games until the time is up. The `time` is a guide rather than a
+/// deadline: a depth that has started always runs to the end, so the answer
+/// can come after the time is up, most of all on large boards.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Request {
+    Protocol,
+    NewGame(NewGameOptions),
+    /// A move to `x`, and to `y` without gravity
+    Move(isize, Option<isize>),
+    Undo,
+    State,
+    Board,
+    Go(SearchLimits),
+    Quit,
+}
+
+/// A line sent back by the engine
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Response {
+    Protocol(u32),
+    Ok,
+    Error(String),
+    State(GameState),
+    Board(Vec<Vec<Option<Player>>>),
+    BestMove(Option<Point>),
+}
+
+/// Why a line could not be understood
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ProtocolError {
+    Empty,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingArgument(&'static str),
+    InvalidArgument {
+        name: &'static str,
+        value: String,
+    },
+    /// Words left over after everything the command takes
+    Unexpected(String),
+}
+
+impl Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("The line is empty"),
+            Self::UnknownCommand(command) => write!(f, "Unknown command {command}"),
+            Self::UnknownOption(option) => write!(f, "Unknown option {option}"),
+            Self::MissingArgument(name) => write!(f, "Missing {name}"),
+            Self::InvalidArgument { name, value } => write!(f, "Invalid {name} {value}"),
+            Self::Unexpected(word) => write!(f, "Unexpected {word}"),
+        }
+    }
+}
+
+impl Error for ProtocolError {}
+
+impl Display for Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Protocol => f.write_str("protocol"),
+            Self::NewGame(new_game) => {
+                write!(
+                    f,
+                    "newgame width={} height={} goal={} players={} placement={}",
+                    new_game.width,
+                    new_game.height,
+                    new_game.goal,
+                    new_game.players,
+                    new_game.placement.to_string().to_lowercase()
+                )?;
+                if let Some(seed) = new_game.seed {
+                    write!(f, " seed={seed}")?;
+                }
+                Ok(())
+            }
+            Self::Move(x, None) => write!(f, "move {x}"),
+            Self::Move(x, Some(y)) => write!(f, "move {x} {y}"),
+            Self::Undo => f.write_str("undo"),
+            Self::State => f.write_str("state"),
+            Self::Board => f.write_str("board"),
+            Self::Go(limits) => {
+                let bot = limits.bot.to_string().to_lowercase().replace(' ', "-");
+                write!(f, "go bot={bot}")?;
+                if let Some(depth) = limits.depth {
+                    write!(f, " depth={depth}")?;
+                }
+                if let Some(time) = limits.time {
+                    write!(f, " time={}", time.as_millis())?;
+                }
+                Ok(())
+            }
+            Self::Quit => f.write_str("quit"),
+        }
+    }
+}
+
+impl FromStr for Request {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = words.next().ok_or(ProtocolError::Empty)?;
+        let request = match command {
+            "protocol" => Self::Protocol,
+            "newgame" => {
+                let mut new_game = NewGameOptions::default();
+                for (name, value) in options(&mut words)? {
+                    match name {
+                        "width" => new_game.width = parse_argument("width", value)?,
+                        "height" => new_game.height = parse_argument("height", value)?,
+                        "goal" => new_game.goal = parse_argument("goal", value)?,
+                        "players" => new_game.players = parse_argument("players", value)?,
+                        "placement" => new_game.placement = parse_argument("placement", value)?,
+                        "seed" => new_game.seed = Some(parse_argument("seed", value)?),
+                        _ => return Err(ProtocolError::UnknownOption(name.to_string())),
+                    }
+                }
+                Self::NewGame(new_game)
+            }
+            "move" => {
+                let x = words.next().ok_or(ProtocolError::MissingArgument("x"))?;
+                let y = words.next().map(|y| parse_argument("y", y)).transpose()?;
+                Self::Move(parse_argument("x", x)?, y)
+            }
+            "undo" => Self::Undo,
+            "state" => Self::State,
+            "board" => Self::Board,
+            "go" => {
+                let mut limits = SearchLimits::default();
+                for (name, value) in options(&mut words)? {
+                    match name {
+                        "bot" => limits.bot = parse_argument("bot", value)?,
+                        "depth" => limits.depth = Some(parse_argument("depth", value)?),
+                        "time" => {
+                            limits.time =
+                                Some(Duration::from_millis(parse_argument("time", value)?))
+                        }
+                        _ => return Err(ProtocolError::UnknownOption(name.to_string())),
+                    }
+                }
+                Self::Go(limits)
+            }
+            "quit" => Self::Quit,
+            _ => return Err(ProtocolError::UnknownCommand(command.to_string())),
+        };
+        match words.next() {
+            Some(word) => Err(ProtocolError::Unexpected(word.to_string())),
+            None => Ok(request),
+        }
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Protocol(version) => write!(f, "protocol {version}"),
+            Self::Ok => f.write_str("ok"),
+            // Keep the answer on one line whatever the message holds
+            Self::Error(message) => write!(f, "error {}", message.replace('\n', " ")),
+            Self::State(GameState::PlayerMove(player)) => write!(f, "state move {player}"),
+            Self::State(GameState::PlayerWon(win)) => write!(f, "state won {}", win.player),
+            Self::State(GameState::Draw) => f.write_str("state draw"),
+            Self::Board(board) => {
+                let rows: Vec<String> = board
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|tile| match tile {
+                                Some(player) => char::from_digit(*player as u32, 36).unwrap_or('?'),
+                                None => '.',
+                            })
+                            .collect()
+                    })
+                    .collect();
+                write!(f, "board {}", rows.join("/"))
+            }
+            Self::BestMove(Some(point)) => write!(f, "bestmove {} {}", point.x, point.y),
+            Self::BestMove(None) => f.write_str("bestmove none"),
+        }
+    }
+}
+
+/// Parses an engine's answer. A won state comes back without its winning
+/// lines, which only the engine knows.
+impl FromStr for Response {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let mut words = rest.split_whitespace();
+        let response = match command {
+            "" => return Err(ProtocolError::Empty),
+            "protocol" => {
+                let version = words
+                    .next()
+                    .ok_or(ProtocolError::MissingArgument("version"))?;
+                Self::Protocol(parse_argument("version", version)?)
+            }
+            "ok" => Self::Ok,
+            "error" => return Ok(Self::Error(rest.trim().to_string())),
+            "state" => match (words.next(), words.next()) {
+                (Some("move"), Some(player)) => {
+                    Self::State(GameState::PlayerMove(parse_argument("player", player)?))
+                }
+                (Some("won"), Some(player)) => Self::State(GameState::PlayerWon(Win {
+                    player: parse_argument("player", player)?,
+                    lines: Vec::new(),
+                })),
+                (Some("draw"), None) => Self::State(GameState::Draw),
+                _ => {
+                    return Err(ProtocolError::InvalidArgument {
+                        name: "state",
+                        value: rest.to_string(),
+                    })
+                }
+            },
+            "board" => {
+                let rows = words
+                    .next()
+                    .ok_or(ProtocolError::MissingArgument("board"))?;
+                let board = rows
+                    .split('/')
+                    .map(|row| {
+                        row.chars()
+                            .map(|tile| match tile {
+                                '.' => Ok(None),
+                                _ => tile
+                                    .to_digit(36)
+                                    .map(|player| Some(player as Player))
+                                    .ok_or(ProtocolError::InvalidArgument {
+                                        name: "tile",
+                                        value: tile.to_string(),
+                                    }),
+                            })
+                            .collect()
+                    })
+                    .collect::<Result<_, _>>()?;
+                Self::Board(board)
+            }
+            "bestmove" => match (words.next(), words.next()) {
+                (Some("none"), None) => Self::BestMove(None),
+                (Some(x), Some(y)) => Self::BestMove(Some(Point::new(
+                    parse_argument("x", x)?,
+                    parse_argument("y", y)?,
+                ))),
+                _ => {
+                    return Err(ProtocolError::InvalidArgument {
+                        name: "move",
+                        value: rest.to_string(),
+                    })
+                }
+            },
+            _ => return Err(ProtocolError::UnknownCommand(command.to_string())),
+        };
+        match words.next() {
+            Some(word) => Err(ProtocolError::Unexpected(word.to_string())),
+            None => Ok(response),
+        }
+    }
+}
+
+/// Splits the remaining words into `name=value` options
+fn options<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+) -> Result<Vec<(&'a str, &'a str)>, ProtocolError> {
+    words
+        .map(|word| {
+            word.split_once('=')
+                .ok_or_else(|| ProtocolError::UnknownOption(word.to_string()))
+        })
+        .collect()
+}
+
+fn parse_argument<T: FromStr>(name: &'static str, value: &str) -> Result<T, ProtocolError> {
+    value.parse().map_err(|_| ProtocolError::InvalidArgument {
+        name,
+        value: value.to_string(),
+    })
+}
+
+/// The engine's side of the protocol: the game being played, changed by each
+/// request in turn
+#[derive(Clone, Debug, Default)]
+pub struct Session {
+    game: Option<Game>,
+    weight_table: Arc<WeightTable>,
+}
+
+impl Session {
+    /// A session whose bots play with `weight_table`
+    pub fn new(weight_table: Arc<WeightTable>) -> Self {
+        Self {
+            game: None,
+            weight_table,
+        }
+    }
+
+    pub fn get_game(&self) -> Option<&Game> {
+        self.game.as_ref()
+    }
+
+    /// Carries out `request`, answering with what the client should be sent
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Protocol => Response::Protocol(PROTOCOL_VERSION),
+            Request::NewGame(new_game) if new_game.players > MAX_PROTOCOL_PLAYERS => {
+                Response::Error(format!(
+                    "There can be at most {MAX_PROTOCOL_PLAYERS} players"
+                ))
+            }
+            Request::NewGame(new_game) => {
+                match Game::new(
+                    new_game.width,
+                    new_game.height,
+                    new_game.goal,
+                    vec![PlayerType::User; new_game.players],
+                    new_game.placement,
+                ) {
+                    Ok(mut game) => {
+                        if let Some(seed) = new_game.seed {
+                            game.set_seed(seed);
+                        }
+                        game.set_weight_table(self.weight_table.clone());
+                        self.game = Some(game);
+                        Response::Ok
+                    }
+                    Err(error) => Response::Error(error.to_string()),
+                }
+            }
+            Request::Quit => Response::Ok,
+            request => {
+                let Some(game) = &mut self.game else {
+                    return Response::Error(String::from(
+                        "There is no game, start one with newgame",
+                    ));
+                };
+                match request {
+                    Request::Move(x, y) => {
+                        if game.get_placement() == Placement::Free && y.is_none() {
+                            return Response::Error(String::from(
+                                "A move without gravity needs a row",
+                            ));
+                        }
+                        match game.play_move(Point::new(x, y.unwrap_or(0))) {
+                            Ok(()) => Response::Ok,
+                            Err(error) => Response::Error(error.to_string()),
+                        }
+                    }
+                    Request::Undo => match game.get_history().last() {
+                        Some(_) => {
+                            game.unplace();
+                            Response::Ok
+                        }
+                        None => Response::Error(String::from("There are no moves to undo")),
+                    },
+                    Request::State => Response::State(game.get_gamestate()),
+                    Request::Board => Response::Board(game.get_board()),
+                    Request::Go(limits) => match game.get_gamestate() {
+                        GameState::PlayerMove(_)
+                            if !limits.bot.get_engine().supports(game.get_player_count()) =>
+                        {
+                            Response::Error(NewGameError::EngineNeedsTwoPlayers.to_string())
+                        }
+                        GameState::PlayerMove(_) => {
+                            Response::BestMove(game.get_limited_move(&limits))
+                        }
+                        state => Response::Error(
+                            super::PlayMoveError::InvalidGameState(state).to_string(),
+                        ),
+                    },
+                    Request::Protocol | Request::NewGame(_) | Request::Quit => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+impl Game {
+    /// The move `limits.bot` would play for the current player, searching no
+    /// deeper and for no longer than `limits` allow
+    pub fn get_limited_move(&self, limits: &SearchLimits) -> Option<Point> {
+        let bot = limits.bot;
+        let player = self.get_current_player();
+        let with_depth = |depth: usize| {
+            let mut game = self.clone();
+            game.set_player_profile(
+                player,
+                Some(Profile {
+                    name: String::from("go"),
+                    weights: self.get_weights(player, bot),
+                    search_depth: Some(depth.clamp(1, MAX_PROFILE_SEARCH_DEPTH)),
+                    search_breadth: self
+                        .get_player_profile(player)
+                        .and_then(|profile| profile.search_breadth),
+                }),
+            );
+            game
+        };
+
+        match (bot.get_engine(), limits.depth, limits.time) {
+            (_, None, None) => self.get_computer_move(bot),
+            (Engine::MonteCarlo, _, Some(time)) => {
+                let mut rng =
+                    StdRng::seed_from_u64(self.get_seed() ^ self.get_history().len() as u64);
+                self.get_monte_carlo_move(
+                    bot,
+                    Budget::Time(time),
+                    bot.get_difficulty().get_playout(),
+                    &mut rng,
+                )
+            }
+            (Engine::Heuristic | Engine::MonteCarlo, _, _) => self.get_computer_move(bot),
+            (_, Some(depth), None) => with_depth(depth).get_computer_move(bot),
+            (_, depth, Some(time)) => {
+                // Deepen until the next search, guessed to take a few times as
+                // long as the last, would run past the limit
+                let start = Instant::now();
+                let mut best = None;
+                for depth in 1..=depth.unwrap_or(MAX_PROFILE_SEARCH_DEPTH) {
+                    let search_start = Instant::now();
+                    best = with_depth(depth).get_computer_move(bot);
+                    let remaining = time.saturating_sub(start.elapsed());
+                    if search_start.elapsed() * 4 > remaining {
+                        break;
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Difficulty, Strategy};
+
+    #[test]
+    fn requests_parse_back_from_their_lines() {
+        let requests = [
+            Request::Protocol,
+            Request::NewGame(NewGameOptions::default()),
+            Request::NewGame(NewGameOptions {
+                width: 19,
+                height: 19,
+                goal: 5,
+                players: 3,
+                placement: Placement::Free,
+                seed: Some(42),
+            }),
+            Request::Move(3, None),
+            Request::Move(0, Some(5)),
+            Request::Undo,
+            Request::State,
+            Request::Board,
+            Request::Go(SearchLimits::default()),
+            Request::Go(SearchLimits {
+                bot: Bot(Difficulty::Insane, Strategy::Offensive, Engine::MonteCarlo),
+                depth: Some(4),
+                time: Some(Duration::from_millis(250)),
+            }),
+            Request::Quit,
+        ];
+        for request in requests {
+            assert_eq!(request.to_string().parse(), Ok(request));
+        }
+    }
+
+    #[test]
+    fn requests_fill_in_defaults_in_any_order() {
+        assert_eq!(
+            "newgame  seed=7 width=9 ".parse(),
+            Ok(Request::NewGame(NewGameOptions {
+                width: 9,
+                seed: Some(7),
+                ..NewGameOptions::default()
+            }))
+        );
+        assert_eq!(
+            "go time=100".parse(),
+            Ok(Request::Go(SearchLimits {
+                time: Some(Duration::from_millis(100)),
+                ..SearchLimits::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn malformed_requests_say_what_is_wrong() {
+        let cases = [
+            ("", ProtocolError::Empty),
+            ("   ", ProtocolError::Empty),
+            ("jump", ProtocolError::UnknownCommand("jump".to_string())),
+            (
+                "newgame size=7",
+                ProtocolError::UnknownOption("size".to_string()),
+            ),
+            ("move", ProtocolError::MissingArgument("x")),
+            (
+                "move left",
+                ProtocolError::InvalidArgument {
+                    name: "x",
+                    value: "left".to_string(),
+                },
+            ),
+            (
+                "go time=soon",
+                ProtocolError::InvalidArgument {
+                    name: "time",
+                    value: "soon".to_string(),
+                },
+            ),
+            ("undo 2", ProtocolError::Unexpected("2".to_string())),
+            ("move 1 2 3", ProtocolError::Unexpected("3".to_string())),
+        ];
+        for (line, error) in cases {
+            assert_eq!(line.parse::<Request>(), Err(error), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn sessions_play_at_most_36_players() {
+        let mut session = Session::default();
+        let new_game = |players| {
+            Request::NewGame(NewGameOptions {
+                width: 100,
+                height: 100,
+                players,
+                ..NewGameOptions::default()
+            })
+        };
+        assert!(matches!(
+            session.handle(new_game(MAX_PROTOCOL_PLAYERS + 1)),
+            Response::Error(_)
+        ));
+        assert!(session.get_game().is_none());
+
+        assert_eq!(session.handle(new_game(MAX_PROTOCOL_PLAYERS)), Response::Ok);
+        for x in 0..MAX_PROTOCOL_PLAYERS as isize {
+            assert_eq!(session.handle(Request::Move(x, None)), Response::Ok);
+        }
+        let Response::Board(board) = session.handle(Request::Board) else {
+            panic!("expected a board");
+        };
+        let line = Response::Board(board).to_string();
+        let bottom = format!("0123456789abcdefghijklmnopqrstuvwxyz{}", ".".repeat(64));
+        assert_eq!(line.rsplit('/').next(), Some(bottom.as_str()));
+    }
+
+    #[test]
+    fn responses_parse_back_from_their_lines() {
+        let responses = [
+            Response::Protocol(PROTOCOL_VERSION),
+            Response::Ok,
+            Response::Error("That move can't be played".to_string()),
+            Response::State(GameState::PlayerMove(1)),
+            Response::State(GameState::PlayerWon(Win {
+                player: 12,
+                lines: Vec::new(),
+            })),
+            Response::State(GameState::Draw),
+            Response::Board(vec![
+                vec![None, Some(0), Some(35)],
+                vec![Some(1), None, None],
+            ]),
+            Response::BestMove(Some(Point::new(3, 5))),
+            Response::BestMove(None),
+        ];
+        for response in responses {
+            assert_eq!(response.to_string().parse(), Ok(response));
+        }
+    }
+
+    #[test]
+    fn error_responses_stay_on_one_line() {
+        let response = Response::Error("first\nsecond".to_string());
+        assert_eq!(response.to_string(), "error first second");
+    }
+
+    #[test]
+    fn malformed_responses_say_what_is_wrong() {
+        let cases = [
+            ("", ProtocolError::Empty),
+            ("hello", ProtocolError::UnknownCommand("hello".to_string())),
+            ("protocol", ProtocolError::MissingArgument("version")),
+            (
+                "state over",
+                ProtocolError::InvalidArgument {
+                    name: "state",
+                    value: "over".to_string(),
+                },
+            ),
+            (
+                "board ..#/...",
+                ProtocolError::InvalidArgument {
+                    name: "tile",
+                    value: "#".to_string(),
+                },
+            ),
+            (
+                "bestmove 3",
+                ProtocolError::InvalidArgument {
+                    name: "move",
+                    value: "3".to_string(),
+                },
+            ),
+        ];
+        for (line, error) in cases {
+            assert_eq!(line.parse::<Response>(), Err(error), "{line:?}");
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Bot, Difficulty, Engine, GameState};
+
+/// Rating given to anyone the first time they finish a game
+pub const DEFAULT_RATING: f64 = 1500.0;
+
+/// Most a rating can move in a two player game. Games with more players share
+/// it between every pairing, so no single game moves a rating further.
+const K_FACTOR: f64 = 32.0;
+
+/// Someone with a rating: a named person, a bot configuration, a bot with a
+/// custom profile or an external engine
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Competitor {
+    Person(String),
+    Computer(Bot),
+    /// A bot using the named profile with the given engine
+    Profile(String, Engine),
+    /// An external engine, by the command that runs it
+    External(String),
+}
+
+impl Display for Competitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Person(name) => f.write_str(name),
+            Self::Computer(bot) => write!(f, "{bot}"),
+            Self::Profile(name, engine) => write!(f, "{name} ({engine})"),
+            Self::External(command) => write!(f, "{command} (external)"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Rating {
+    pub competitor: Competitor,
+    pub rating: f64,
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Rating {
+    fn new(competitor: Competitor) -> Self {
+        Self {
+            competitor,
+            rating: DEFAULT_RATING,
+            games: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+}
+
+/// Elo ratings built up from finished games.
+///
+/// A game with more than two players is scored as a two player game between
+/// every pair of them: the winner beats everyone else, and everyone else draws
+/// with each other.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    ratings: Vec<Rating>,
+}
+
+/// Why ratings could not be read or written
+#[derive(Debug)]
+pub enum RatingsError {
+    Io(io::Error),
+    Format(serde_json::Error),
+}
+
+impl Display for RatingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not access the ratings file: {error}"),
+            Self::Format(error) => write!(f, "The ratings file is corrupt: {error}"),
+        }
+    }
+}
+
+impl Error for RatingsError {}
+
+impl Ratings {
+    /// Reads the ratings at `path`, starting afresh if there is no file yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RatingsError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(RatingsError::Format),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(RatingsError::Io(error)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RatingsError> {
+        let contents = serde_json::to_string_pretty(self).map_err(RatingsError::Format)?;
+        fs::write(path, contents).map_err(RatingsError::Io)
+    }
+
+    pub fn get(&self, competitor: &Competitor) -> Option<&Rating> {
+        self.ratings
+            .iter()
+            .find(|rating| &rating.competitor == competitor)
+    }
+
+    /// Every rating, from highest to lowest
+    pub fn get_leaderboard(&self) -> Vec<&Rating> {
+        let mut leaderboard: Vec<_> = self.ratings.iter().collect();
+        leaderboard.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        leaderboard
+    }
+
+    /// The average rating of the bots at each difficulty, weighted by how many
+    /// games each has played, for the difficulties that have played any
+    pub fn get_difficulty_ratings(&self) -> Vec<(Difficulty, f64)> {
+        Difficulty::ALL
+            .iter()
+            .filter_map(|&difficulty| {
+                let (total, games) = self
+                    .ratings
+                    .iter()
+                    .filter(|rating| {
+                        matches!(rating.competitor, Competitor::Computer(bot) if bot.get_difficulty() == difficulty)
+                    })
+                    .fold((0.0, 0), |(total, games), rating| {
+                        (total + rating.rating * rating.games as f64, games + rating.games)
+                    });
+                (games > 0).then(|| (difficulty, total / games as f64))
+            })
+            .collect()
+    }
+
+    /// Updates the ratings of everyone in a finished game, where `competitors`
+    /// is in the order the players took their turns. Nothing changes if the game
+    /// is still going.
+    ///
+    /// A competitor in several seats counts once, winning if any of its seats
+    /// won, so a game between one competitor's seats changes nothing.
+    pub fn record_game(&mut self, competitors: &[Competitor], state: &GameState) {
+        let winner = match state {
+            GameState::PlayerMove(_) => return,
+            GameState::PlayerWon(win) => competitors.get(win.player),
+            GameState::Draw => None,
+        };
+
+        let mut entrants: Vec<&Competitor> = Vec::new();
+        for competitor in competitors {
+            if !entrants.contains(&competitor) {
+                entrants.push(competitor);
+            }
+        }
+        if entrants.len() < 2 {
+            return;
+        }
+
+        let indices: Vec<_> = entrants
+            .iter()
+            .map(|competitor| self.get_index(competitor))
+            .collect();
+        let before: Vec<_> = indices
+            .iter()
+            .map(|&index| self.ratings[index].rating)
+            .collect();
+        let k = K_FACTOR / (entrants.len() - 1) as f64;
+
+        let won = |entrant: usize| winner == Some(entrants[entrant]);
+        for (entrant, &index) in indices.iter().enumerate() {
+            let change: f64 = (0..entrants.len())
+                .filter(|&opponent| opponent != entrant)
+                .map(|opponent| {
+                    let score = if won(entrant) {
+                        1.0
+                    } else if won(opponent) {
+                        0.0
+                    } else {
+                        0.5
+                    };
+                    let expected =
+                        1.0 / (1.0 + 10f64.powf((before[opponent] - before[entrant]) / 400.0));
+                    k * (score - expected)
+                })
+                .sum();
+
+            let rating = &mut self.ratings[index];
+            rating.rating += change;
+            rating.games += 1;
+            match winner {
+                Some(_) if won(entrant) => rating.wins += 1,
+                Some(_) => rating.losses += 1,
+                None => rating.draws += 1,
+            }
+        }
+    }
+
+    /// Where `competitor` is in the list, adding them if they are new
+    fn get_index(&mut self, competitor: &Competitor) -> usize {
+        match self
+            .ratings
+            .iter()
+            .position(|rating| &rating.competitor == competitor)
+        {
+            Some(index) => index,
+            None => {
+                self.ratings.push(Rating::new(competitor.clone()));
+                self.ratings.len() - 1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Line, Point, Win};
+
+    fn person(name: &str) -> Competitor {
+        Competitor::Person(name.to_string())
+    }
+
+    fn won_by(player: usize) -> GameState {
+        GameState::PlayerWon(Win {
+            player,
+            lines: vec![Line {
+                start: Point::new(0, 0),
+                direction: Point::new(1, 0),
+                length: 4,
+            }],
+        })
+    }
+
+    /// Ratings where each person starts at the given rating
+    fn ratings(start: &[(&str, f64)]) -> Ratings {
+        Ratings {
+            ratings: start
+                .iter()
+                .map(|&(name, rating)| Rating {
+                    rating,
+                    ..Rating::new(person(name))
+                })
+                .collect(),
+        }
+    }
+
+    fn rating(ratings: &Ratings, name: &str) -> f64 {
+        ratings.get(&person(name)).unwrap().rating
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn a_win_moves_two_players_by_the_expected_amount() {
+        let mut ratings = ratings(&[("Ann", 1600.0), ("Bob", 1400.0)]);
+        ratings.record_game(&[person("Ann"), person("Bob")], &won_by(1));
+        // Ann was expected to score 1 / (1 + 10^(-200 / 400)) = 0.7597
+        assert_close(rating(&ratings, "Ann"), 1600.0 - 32.0 * 0.7597);
+        assert_close(rating(&ratings, "Bob"), 1400.0 + 32.0 * 0.7597);
+
+        let ann = ratings.get(&person("Ann")).unwrap();
+        assert_eq!((ann.games, ann.wins, ann.draws, ann.losses), (1, 0, 0, 1));
+    }
+
+    #[test]
+    fn newcomers_start_at_the_default_rating() {
+        let mut ratings = Ratings::default();
+        ratings.record_game(&[person("Ann"), person("Bob")], &won_by(0));
+        assert_close(rating(&ratings, "Ann"), DEFAULT_RATING + 16.0);
+        assert_close(rating(&ratings, "Bob"), DEFAULT_RATING - 16.0);
+    }
+
+    #[test]
+    fn a_draw_moves_the_stronger_player_down() {
+        let mut ratings = ratings(&[("Ann", 1600.0), ("Bob", 1400.0)]);
+        ratings.record_game(&[person("Ann"), person("Bob")], &GameState::Draw);
+        assert_close(rating(&ratings, "Ann"), 1600.0 - 32.0 * (0.7597 - 0.5));
+        assert_close(rating(&ratings, "Bob"), 1400.0 + 32.0 * (0.7597 - 0.5));
+        assert_eq!(ratings.get(&person("Bob")).unwrap().draws, 1);
+    }
+
+    #[test]
+    fn equal_players_share_a_multiplayer_result_evenly() {
+        let names = ["Ann", "Bob", "Cat", "Dan"];
+        let competitors: Vec<_> = names.iter().map(|name| person(name)).collect();
+
+        let mut ratings = Ratings::default();
+        ratings.record_game(&competitors, &won_by(2));
+        // The winner beats three players with a third of the usual K each
+        assert_close(rating(&ratings, "Cat"), DEFAULT_RATING + 16.0);
+        for name in ["Ann", "Bob", "Dan"] {
+            assert_close(rating(&ratings, name), DEFAULT_RATING - 16.0 / 3.0);
+        }
+
+        let mut ratings = Ratings::default();
+        ratings.record_game(&competitors, &GameState::Draw);
+        for name in names {
+            assert_close(rating(&ratings, name), DEFAULT_RATING);
+        }
+    }
+
+    #[test]
+    fn a_competitor_in_several_seats_counts_once() {
+        let mut ratings = Ratings::default();
+        ratings.record_game(&[person("Ann"), person("Bob"), person("Ann")], &won_by(2));
+        assert_close(rating(&ratings, "Ann"), DEFAULT_RATING + 16.0);
+        assert_close(rating(&ratings, "Bob"), DEFAULT_RATING - 16.0);
+        let ann = ratings.get(&person("Ann")).unwrap();
+        assert_eq!((ann.games, ann.wins, ann.losses), (1, 1, 0));
+    }
+
+    #[test]
+    fn playing_only_yourself_changes_nothing() {
+        let mut ratings = Ratings::default();
+        ratings.record_game(&[person("Ann"), person("Ann")], &won_by(0));
+        assert!(ratings.get(&person("Ann")).is_none());
+    }
+
+    #[test]
+    fn unfinished_games_change_nothing() {
+        let mut ratings = Ratings::default();
+        ratings.record_game(&[person("Ann"), person("Bob")], &GameState::PlayerMove(0));
+        assert!(ratings.get_leaderboard().is_empty());
+    }
+}
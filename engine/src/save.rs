@@ -0,0 +1,424 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Game, GameState, GameTrait, Move, NewGameError, Placement, PlayMoveError, Player, PlayerType,
+    Profile, ProfileError,
+};
+
+/// Bumped whenever the layout of [`SaveFile`] changes, so newer files are
+/// rejected instead of being misread. Every older version can still be loaded.
+pub const SAVE_VERSION: u32 = 5;
+
+/// Everything needed to restore a game, as written to disk.
+///
+/// The programs of external players are left out, so opening a file someone
+/// else wrote never starts a program they chose.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub width: isize,
+    pub height: isize,
+    pub goal: isize,
+    pub placement: Placement,
+    pub players: Vec<PlayerType>,
+    pub moves: Vec<Move>,
+    pub state: GameState,
+    /// Missing from version 1 files, which get a new random seed
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// The custom profile of each player's bot, if it has one. Missing from
+    /// files before version 3.
+    #[serde(default)]
+    pub profiles: Vec<Option<Profile>>,
+    /// How many hints each player asked for. Missing from files before
+    /// version 4.
+    #[serde(default)]
+    pub hints: Vec<usize>,
+}
+
+/// Why a game could not be saved
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(serde_json::Error),
+}
+
+/// Why a save file could not be turned back into a game
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    UnsupportedVersion(u32),
+    InvalidSettings(NewGameError),
+    InvalidProfile(ProfileError),
+    IllegalMove(usize, PlayMoveError),
+    WrongPlayer {
+        index: usize,
+        expected: Player,
+        found: Player,
+    },
+    /// The move was recorded somewhere other than where the piece would land
+    WrongLanding(usize),
+    StateMismatch {
+        saved: GameState,
+        replayed: GameState,
+    },
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not write the save file: {error}"),
+            Self::Serialize(error) => write!(f, "Could not encode the game: {error}"),
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read the save file: {error}"),
+            Self::Parse(error) => write!(f, "The save file is corrupt: {error}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "The save file is version {version}, but only versions 1 to {SAVE_VERSION} are supported"
+            ),
+            Self::InvalidSettings(error) => {
+                write!(f, "The saved game has invalid settings: {error}")
+            }
+            Self::InvalidProfile(error) => {
+                write!(f, "The saved game has an invalid bot profile: {error}")
+            }
+            Self::IllegalMove(index, error) => {
+                write!(f, "Move {} in the save file is illegal: {error}", index + 1)
+            }
+            Self::WrongPlayer {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Move {} in the save file was played by player {}, but it was player {}'s turn",
+                index + 1,
+                found + 1,
+                expected + 1
+            ),
+            Self::WrongLanding(index) => write!(
+                f,
+                "Move {} in the save file is not where the piece would land",
+                index + 1
+            ),
+            Self::StateMismatch { saved, replayed } => write!(
+                f,
+                "The saved result {saved:?} does not match the moves, which give {replayed:?}"
+            ),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<&Game> for SaveFile {
+    fn from(game: &Game) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            width: game.get_width(),
+            height: game.get_height(),
+            goal: game.get_goal(),
+            placement: game.get_placement(),
+            players: game.get_players().to_vec(),
+            moves: game.get_history().to_vec(),
+            state: game.get_gamestate(),
+            seed: Some(game.get_seed()),
+            profiles: (0..game.get_player_count())
+                .map(|player| game.get_player_profile(player).cloned())
+                .collect(),
+            hints: game.get_hint_counts().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<SaveFile> for Game {
+    type Error = LoadError;
+
+    /// Rebuilds the game by replaying every saved move through the rules
+    fn try_from(save: SaveFile) -> Result<Self, Self::Error> {
+        if !(1..=SAVE_VERSION).contains(&save.version) {
+            return Err(LoadError::UnsupportedVersion(save.version));
+        }
+
+        let mut game = Game::new(
+            save.width,
+            save.height,
+            save.goal,
+            save.players,
+            save.placement,
+        )
+        .map_err(LoadError::InvalidSettings)?;
+        if let Some(seed) = save.seed {
+            game.set_seed(seed);
+        }
+        for (player, profile) in save.profiles.into_iter().enumerate() {
+            if let Some(error) = profile
+                .as_ref()
+                .and_then(|profile| profile.validate(player).into_iter().next())
+            {
+                return Err(LoadError::InvalidProfile(error));
+            }
+            if player < game.get_player_count() {
+                game.set_player_profile(player, profile);
+            }
+        }
+
+        game.set_hint_counts(save.hints);
+
+        for (index, m) in save.moves.into_iter().enumerate() {
+            let player = match game.get_gamestate() {
+                GameState::PlayerMove(player) => player,
+                state => {
+                    return Err(LoadError::IllegalMove(
+                        index,
+                        PlayMoveError::InvalidGameState(state),
+                    ))
+                }
+            };
+            if m.player != player {
+                return Err(LoadError::WrongPlayer {
+                    index,
+                    expected: player,
+                    found: m.player,
+                });
+            }
+            let point = game
+                .get_landing_point(&m.point)
+                .map_err(|error| LoadError::IllegalMove(index, error))?;
+            if point != m.point {
+                return Err(LoadError::WrongLanding(index));
+            }
+            game.place(point, player, m.bot);
+        }
+
+        let replayed = game.get_gamestate();
+        if replayed != save.state {
+            return Err(LoadError::StateMismatch {
+                saved: save.state,
+                replayed,
+            });
+        }
+
+        Ok(game)
+    }
+}
+
+impl Game {
+    /// Writes the game to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        let contents =
+            serde_json::to_string_pretty(&SaveFile::from(self)).map_err(SaveError::Serialize)?;
+        fs::write(path, contents).map_err(SaveError::Io)
+    }
+
+    /// Reads a game written by [`Game::save`], replaying its moves to check them
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let contents = fs::read_to_string(path).map_err(LoadError::Io)?;
+        let save: SaveFile = serde_json::from_str(&contents).map_err(LoadError::Parse)?;
+        Game::try_from(save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{Bot, ComputerWeights, Point};
+
+    /// A game where the first player has won with a column of four
+    fn won_game() -> Game {
+        let mut game = Game::new(
+            7,
+            6,
+            4,
+            vec![PlayerType::User, PlayerType::Computer(Bot::default())],
+            Placement::Gravity,
+        )
+        .unwrap();
+        for column in [0, 1, 0, 1, 0, 1, 0] {
+            game.play_column(column).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn saved_games_load_as_they_were() {
+        let mut game = won_game();
+        game.set_seed(42);
+        game.set_hint_counts(vec![2, 0]);
+        let path = env::temp_dir().join(format!("four-in-a-row-save-{}.json", std::process::id()));
+        game.save(&path).unwrap();
+        let loaded = Game::load(&path);
+        fs::remove_file(&path).ok();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.get_history(), game.get_history());
+        assert_eq!(loaded.get_gamestate(), game.get_gamestate());
+        assert_eq!(loaded.get_players(), game.get_players());
+        assert_eq!(loaded.get_seed(), 42);
+        assert_eq!(loaded.get_hint_counts(), [2, 0]);
+    }
+
+    #[test]
+    fn missing_files_fail_to_read() {
+        let path = env::temp_dir().join("four-in-a-row-no-such-save.json");
+        assert!(matches!(Game::load(path), Err(LoadError::Io(_))));
+    }
+
+    #[test]
+    fn corrupt_files_fail_to_parse() {
+        let path =
+            env::temp_dir().join(format!("four-in-a-row-corrupt-{}.json", std::process::id()));
+        fs::write(&path, "{\"version\": 5, \"width\": ").unwrap();
+        let loaded = Game::load(&path);
+        fs::remove_file(&path).ok();
+        assert!(matches!(loaded, Err(LoadError::Parse(_))));
+    }
+
+    #[test]
+    fn versions_out_of_range_are_unsupported() {
+        for version in [0, SAVE_VERSION + 1] {
+            let save = SaveFile {
+                version,
+                ..SaveFile::from(&won_game())
+            };
+            assert!(matches!(
+                Game::try_from(save),
+                Err(LoadError::UnsupportedVersion(v)) if v == version
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let save = SaveFile {
+            players: vec![PlayerType::User],
+            moves: Vec::new(),
+            state: GameState::PlayerMove(0),
+            ..SaveFile::from(&won_game())
+        };
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::InvalidSettings(
+                NewGameError::PlayersMustBeAtLeast2
+            ))
+        ));
+
+        let save = SaveFile {
+            width: isize::MAX,
+            moves: Vec::new(),
+            state: GameState::PlayerMove(0),
+            ..SaveFile::from(&won_game())
+        };
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::InvalidSettings(NewGameError::WidthTooLarge))
+        ));
+    }
+
+    #[test]
+    fn invalid_profiles_are_rejected() {
+        let profile = Profile {
+            name: String::from("Deep"),
+            weights: ComputerWeights::built_in(Default::default(), Default::default()),
+            search_depth: Some(0),
+            search_breadth: None,
+        };
+        let save = SaveFile {
+            profiles: vec![None, Some(profile)],
+            ..SaveFile::from(&won_game())
+        };
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::InvalidProfile(
+                ProfileError::SearchDepthOutOfRange(_, 0)
+            ))
+        ));
+    }
+
+    #[test]
+    fn moves_after_the_end_are_illegal() {
+        let mut save = SaveFile::from(&won_game());
+        save.moves.push(Move {
+            point: Point::new(2, 5),
+            player: 1,
+            bot: None,
+        });
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::IllegalMove(
+                7,
+                PlayMoveError::InvalidGameState(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn moves_into_a_full_column_are_illegal() {
+        let mut game = Game::new(7, 6, 4, vec![PlayerType::User; 3], Placement::Gravity).unwrap();
+        for _ in 0..6 {
+            game.play_column(0).unwrap();
+        }
+        let mut save = SaveFile::from(&game);
+        save.moves.push(Move {
+            point: Point::new(0, 0),
+            player: 0,
+            bot: None,
+        });
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::IllegalMove(6, PlayMoveError::ColumnIsFull(0)))
+        ));
+    }
+
+    #[test]
+    fn moves_out_of_turn_are_rejected() {
+        let mut save = SaveFile::from(&won_game());
+        save.moves[1].player = 0;
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::WrongPlayer {
+                index: 1,
+                expected: 1,
+                found: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn floating_pieces_are_rejected() {
+        let mut save = SaveFile::from(&won_game());
+        save.moves[0].point = Point::new(0, 0);
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::WrongLanding(0))
+        ));
+    }
+
+    #[test]
+    fn results_that_do_not_follow_from_the_moves_are_rejected() {
+        let save = SaveFile {
+            state: GameState::Draw,
+            ..SaveFile::from(&won_game())
+        };
+        assert!(matches!(
+            Game::try_from(save),
+            Err(LoadError::StateMismatch {
+                saved: GameState::Draw,
+                replayed: GameState::PlayerWon(_)
+            })
+        ));
+    }
+}
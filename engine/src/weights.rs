@@ -0,0 +1,235 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Bot, Difficulty, Strategy};
+
+/// How a bot scores an empty cell: every line through it that could still be
+/// completed counts for the player whose pieces are in it
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct ComputerWeights {
+    /// Multiplier for lines of the bot's own pieces
+    pub computer: u64,
+    /// Multiplier for lines of an opponent's pieces
+    pub opponent: u64,
+    /// Value of each empty cell in a line
+    pub empty: u64,
+    /// Value of each piece in a line
+    pub populated: u64,
+    /// Base raised to the number of pieces in a line of the bot's own
+    pub streak_computer: u64,
+    /// Base raised to the number of pieces in an opponent's line
+    pub streak_opponent: u64,
+}
+
+impl ComputerWeights {
+    /// The weights compiled into the game for each difficulty and strategy
+    pub fn built_in(difficulty: Difficulty, strategy: Strategy) -> Self {
+        match (difficulty, strategy) {
+            (Difficulty::Easy, Strategy::Neutral) => Self {
+                computer: 1,
+                opponent: 1,
+                empty: 0,
+                populated: 0,
+                streak_computer: 1,
+                streak_opponent: 1,
+            },
+            (Difficulty::Easy, Strategy::Defensive) => Self {
+                computer: 1,
+                opponent: 1,
+                empty: 0,
+                populated: 1,
+                streak_computer: 1,
+                streak_opponent: 1,
+            },
+            (Difficulty::Easy, Strategy::Offensive) => Self {
+                computer: 1,
+                opponent: 1,
+                empty: 1,
+                populated: 0,
+                streak_computer: 1,
+                streak_opponent: 1,
+            },
+            (Difficulty::Normal, Strategy::Neutral) => Self {
+                computer: 1,
+                opponent: 1,
+                empty: 1,
+                populated: 1,
+                streak_computer: 2,
+                streak_opponent: 2,
+            },
+            (Difficulty::Normal, Strategy::Defensive) => Self {
+                computer: 4,
+                opponent: 5,
+                empty: 1,
+                populated: 1,
+                streak_computer: 2,
+                streak_opponent: 1,
+            },
+            (Difficulty::Normal, Strategy::Offensive) => Self {
+                computer: 5,
+                opponent: 4,
+                empty: 1,
+                populated: 1,
+                streak_computer: 1,
+                streak_opponent: 2,
+            },
+            (Difficulty::Hard, Strategy::Neutral) => Self {
+                computer: 4,
+                opponent: 5,
+                empty: 1,
+                populated: 2,
+                streak_computer: 2,
+                streak_opponent: 2,
+            },
+            (Difficulty::Hard, Strategy::Defensive) => Self {
+                computer: 2,
+                opponent: 3,
+                empty: 1,
+                populated: 2,
+                streak_computer: 2,
+                streak_opponent: 2,
+            },
+            (Difficulty::Hard, Strategy::Offensive) => Self {
+                computer: 3,
+                opponent: 2,
+                empty: 1,
+                populated: 2,
+                streak_computer: 2,
+                streak_opponent: 2,
+            },
+            (Difficulty::Insane, Strategy::Neutral) => Self {
+                computer: 1,
+                opponent: 2,
+                empty: 1,
+                populated: 3,
+                streak_computer: 5,
+                streak_opponent: 6,
+            },
+            (Difficulty::Insane, Strategy::Defensive) => Self {
+                computer: 1,
+                opponent: 4,
+                empty: 1,
+                populated: 3,
+                streak_computer: 2,
+                streak_opponent: 4,
+            },
+            (Difficulty::Insane, Strategy::Offensive) => Self {
+                computer: 3,
+                opponent: 2,
+                empty: 1,
+                populated: 3,
+                streak_computer: 4,
+                streak_opponent: 3,
+            },
+        }
+    }
+}
+
+/// Weights for every difficulty and strategy
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WeightTable {
+    entries: Vec<WeightEntry>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WeightEntry {
+    pub difficulty: Difficulty,
+    pub strategy: Strategy,
+    pub weights: ComputerWeights,
+}
+
+/// Why a weight table could not be read or written
+#[derive(Debug)]
+pub enum WeightsError {
+    Io(io::Error),
+    Format(serde_json::Error),
+}
+
+impl Display for WeightsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not access the weights file: {error}"),
+            Self::Format(error) => write!(f, "The weights file is invalid: {error}"),
+        }
+    }
+}
+
+impl Error for WeightsError {}
+
+/// The built-in weights
+impl Default for WeightTable {
+    fn default() -> Self {
+        Self {
+            entries: Difficulty::ALL
+                .iter()
+                .flat_map(|&difficulty| {
+                    Strategy::ALL.iter().map(move |&strategy| WeightEntry {
+                        difficulty,
+                        strategy,
+                        weights: ComputerWeights::built_in(difficulty, strategy),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WeightTable {
+    /// Reads a table written by [`WeightTable::save`], using the built-in
+    /// weights if there is no file. Entries missing from the file also keep
+    /// their built-in weights.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, WeightsError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                let loaded: Self = serde_json::from_str(&contents).map_err(WeightsError::Format)?;
+                let mut table = Self::default();
+                for entry in loaded.entries {
+                    table.set(entry.difficulty, entry.strategy, entry.weights);
+                }
+                Ok(table)
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(WeightsError::Io(error)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), WeightsError> {
+        let contents = serde_json::to_string_pretty(self).map_err(WeightsError::Format)?;
+        fs::write(path, contents).map_err(WeightsError::Io)
+    }
+
+    pub fn get(&self, difficulty: Difficulty, strategy: Strategy) -> ComputerWeights {
+        self.entries
+            .iter()
+            .find(|entry| entry.difficulty == difficulty && entry.strategy == strategy)
+            .map_or_else(
+                || ComputerWeights::built_in(difficulty, strategy),
+                |entry| entry.weights,
+            )
+    }
+
+    /// The weights `bot` plays with
+    pub fn get_bot_weights(&self, bot: Bot) -> ComputerWeights {
+        self.get(bot.get_difficulty(), bot.get_strategy())
+    }
+
+    pub fn set(&mut self, difficulty: Difficulty, strategy: Strategy, weights: ComputerWeights) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.difficulty == difficulty && entry.strategy == strategy)
+        {
+            Some(entry) => entry.weights = weights,
+            None => self.entries.push(WeightEntry {
+                difficulty,
+                strategy,
+                weights,
+            }),
+        }
+    }
+
+    pub fn get_entries(&self) -> &[WeightEntry] {
+        &self.entries
+    }
+}
@@ -0,0 +1,131 @@
+#!/usr/bin/env python3
+"""A stand-in external engine that plays random legal moves.
+
+It speaks the line protocol documented on `Request` in the engine crate, and
+can be told to misbehave to try out how a game copes with a broken engine:
+
+    python3 scripts/mock_engine.py                  # plays random moves
+    python3 scripts/mock_engine.py --crash-after 3  # exits on its 4th go
+    python3 scripts/mock_engine.py --hang-after 3   # never answers its 4th go
+    python3 scripts/mock_engine.py --close-after 3  # closes stdout on its 4th go
+    python3 scripts/mock_engine.py --illegal        # answers go with a full cell
+    python3 scripts/mock_engine.py --delay 500      # thinks for 500ms per go
+    python3 scripts/mock_engine.py --version 2      # claims another protocol
+"""
+
+import argparse
+import os
+import random
+import sys
+import time
+
+
+class Board:
+    def __init__(self, width=7, height=6, gravity=True):
+        self.width = width
+        self.height = height
+        self.gravity = gravity
+        self.cells = {}
+        self.moves = []
+        self.players = 2
+
+    def landing(self, x, y):
+        if not 0 <= x < self.width:
+            return None
+        if self.gravity:
+            for row in reversed(range(self.height)):
+                if (x, row) not in self.cells:
+                    return (x, row)
+            return None
+        if y is None or not 0 <= y < self.height or (x, y) in self.cells:
+            return None
+        return (x, y)
+
+    def play(self, x, y):
+        point = self.landing(x, y)
+        if point is None:
+            return False
+        self.cells[point] = len(self.moves) % self.players
+        self.moves.append(point)
+        return True
+
+    def valid_moves(self):
+        points = (self.landing(x, y) for x in range(self.width) for y in range(self.height))
+        return sorted({point for point in points if point is not None})
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--crash-after", type=int, help="exit after answering this many go requests")
+    parser.add_argument("--hang-after", type=int, help="stop answering after this many go requests")
+    parser.add_argument("--close-after", type=int, help="close stdout but keep running after this many go requests")
+    parser.add_argument("--illegal", action="store_true", help="answer go with a cell that is taken")
+    parser.add_argument("--delay", type=int, default=0, help="milliseconds to wait before each bestmove")
+    parser.add_argument("--version", type=int, default=1, help="protocol version to claim")
+    parser.add_argument("--seed", type=int, help="seed for the random moves")
+    args = parser.parse_args()
+
+    rng = random.Random(args.seed)
+    board = Board()
+    searches = 0
+
+    def answer(line):
+        print(line, flush=True)
+
+    for line in sys.stdin:
+        words = line.split()
+        if not words:
+            answer("error The line is empty")
+            continue
+        command, rest = words[0], words[1:]
+        options = dict(word.split("=", 1) for word in rest if "=" in word)
+
+        if command == "protocol":
+            answer(f"protocol {args.version}")
+        elif command == "newgame":
+            board = Board(
+                int(options.get("width", 7)),
+                int(options.get("height", 6)),
+                options.get("placement", "gravity").lower() == "gravity",
+            )
+            board.players = int(options.get("players", 2))
+            answer("ok")
+        elif command == "move":
+            y = int(rest[1]) if len(rest) > 1 else None
+            answer("ok" if board.play(int(rest[0]), y) else "error That move can't be played")
+        elif command == "undo":
+            if board.moves:
+                del board.cells[board.moves.pop()]
+                answer("ok")
+            else:
+                answer("error There is nothing to undo")
+        elif command == "go":
+            if args.crash_after is not None and searches >= args.crash_after:
+                sys.exit(3)
+            if args.hang_after is not None and searches >= args.hang_after:
+                while True:
+                    time.sleep(60)
+            if args.close_after is not None and searches >= args.close_after:
+                sys.stdout.flush()
+                os.close(sys.stdout.fileno())
+                while True:
+                    time.sleep(60)
+            searches += 1
+            time.sleep(args.delay / 1000)
+            if args.illegal and board.moves:
+                x, y = board.moves[0]
+                answer(f"bestmove {x} {y}")
+            elif board.valid_moves():
+                x, y = rng.choice(board.valid_moves())
+                answer(f"bestmove {x} {y}")
+            else:
+                answer("bestmove none")
+        elif command == "quit":
+            answer("ok")
+            return
+        else:
+            answer(f"error Unknown command {command}")
+
+
+if __name__ == "__main__":
+    main()
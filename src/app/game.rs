@@ -1,17 +1,140 @@
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
 use iced::{
+    event,
+    futures::channel::oneshot,
+    keyboard::{self, KeyCode},
     theme,
-    widget::{button, column, container, row},
-    Color, Length, Theme, mouse::Button,
+    widget::{
+        button, checkbox, column, container, pick_list, row, text, text_input, tooltip, Space,
+    },
+    Color, Command, Event, Length, Theme,
 };
 
-use crate::{
-    game::{Game, GameTrait, Point},
-    Message,
+use four_in_a_row_engine::{
+    Bot, Competitor, ComputerWeights, Difficulty, ExternalEngine, ExternalEngineError, Game,
+    GameClient, GameState, GameTrait, Hint, HostEvent, HostMessage, HostUpdate, Placement, Player,
+    PlayerType, Point, Ratings, Strategy, DEFAULT_RATING, PLAYER_COLORS,
 };
 
+use crate::Message;
+
+use super::{new_session, GameSettings, Network, RATINGS_PATH};
+
+pub struct GameScreen {
+    game: Game,
+    /// The settings the game was started from, returned to when changing settings
+    settings: GameSettings,
+    /// Minimum time each computer move takes, so games between bots can be followed
+    delay: Duration,
+    thinking: bool,
+    /// Players whose bot panicked while choosing a move, which are not asked
+    /// again until the rematch
+    crashed: Vec<Player>,
+    /// Bumped whenever the board changes under a pending computer move, so its
+    /// result is thrown away when it arrives
+    generation: usize,
+    save_path: String,
+    /// Outcome of the last save, shown next to the save button
+    save_status: String,
+    rotate_players: bool,
+    /// Who is playing each seat, for the ratings
+    competitors: Vec<Competitor>,
+    /// Whether the game's result has gone into the ratings, so it only counts once
+    rated: bool,
+    /// The rating changes from the finished game, or why they could not be saved
+    rating_status: String,
+    /// The move recommended to the player whose turn it is, until they move
+    hint: Option<Hint>,
+    finding_hint: bool,
+    /// Whether empty cells are colored by how the bots score them
+    heatmap: bool,
+    heatmap_weights: HeatmapWeights,
+    /// The running program of each external player, shared with the thread
+    /// asking it for a move
+    engines: Vec<Option<Arc<Mutex<ExternalEngine>>>>,
+    /// Why an external player's fallback bot last had to move for it
+    engine_status: String,
+    /// The other instances the game is played with, if any
+    network: Option<Network>,
+    /// The connection events are currently accepted from
+    session: usize,
+    /// The last thing that happened on the network, such as a player leaving
+    network_status: String,
+    /// Whether each seat has someone playing it, as last told by the host
+    present: Vec<bool>,
+    /// How many spectators are following a network game
+    spectators: usize,
+}
+
+/// Whose weights the heatmap scores the cells with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeatmapWeights {
+    /// The weights of the player to move, or of the default bot for people
+    CurrentPlayer,
+    Bot(Difficulty, Strategy),
+    Profile(String),
+}
+
+impl Display for HeatmapWeights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CurrentPlayer => f.write_str("Current player"),
+            Self::Bot(difficulty, strategy) => write!(f, "{difficulty} {strategy}"),
+            Self::Profile(name) => f.write_str(name),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum GameMessage {
     PlayMove(Point),
+    /// A computer move, with why the player's external engine failed if it did
+    ComputerMove(usize, Option<Point>, Option<String>),
+    /// The bot of a player panicked while choosing its move
+    BotCrashed(usize, Player),
+    /// Something happened on a hosted game's connections, or `None` once
+    /// hosting has stopped
+    HostEvent(usize, Option<HostEvent>),
+    /// A message from the host of a joined game, or `None` once the connection
+    /// is lost
+    FromHost(usize, Option<HostMessage>),
+    Reconnect,
+    Hint,
+    ShowHint(usize, Option<Hint>),
+    SetHeatmap(bool),
+    SetHeatmapWeights(HeatmapWeights),
+    Undo,
+    Redo,
+    SetSavePath(String),
+    Save,
+    SetRotatePlayers(bool),
+    Rematch,
+}
+
+impl GameMessage {
+    pub fn from_event(event: Event, status: event::Status) -> Option<Message> {
+        match (event, status) {
+            (
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code,
+                    modifiers,
+                }),
+                event::Status::Ignored,
+            ) if modifiers.command() => match key_code {
+                KeyCode::Z if modifiers.shift() => Some(GameMessage::Redo.into()),
+                KeyCode::Z => Some(GameMessage::Undo.into()),
+                KeyCode::Y => Some(GameMessage::Redo.into()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl From<GameMessage> for Message {
@@ -20,10 +143,203 @@ impl From<GameMessage> for Message {
     }
 }
 
-impl Game {
+impl GameScreen {
+    /// A screen for playing `game`, hosted or joined over `network` if given
+    pub fn new(game: Game, settings: GameSettings, network: Option<Network>) -> Self {
+        let present = match &network {
+            Some(Network::Host(host)) => host.get_present(&game),
+            _ => vec![true; game.get_player_count()],
+        };
+        Self {
+            engines: start_engines(&game),
+            competitors: settings.get_competitors(&game),
+            // Only the host of a network game records it
+            rated: !matches!(game.get_gamestate(), GameState::PlayerMove(_))
+                || network.as_ref().is_some_and(Network::is_client),
+            rating_status: String::new(),
+            game,
+            delay: settings.get_delay(),
+            save_path: settings.get_save_path().to_string(),
+            settings,
+            thinking: false,
+            crashed: Vec::new(),
+            generation: 0,
+            save_status: String::new(),
+            rotate_players: false,
+            hint: None,
+            finding_hint: false,
+            heatmap: false,
+            heatmap_weights: HeatmapWeights::CurrentPlayer,
+            engine_status: String::new(),
+            present,
+            spectators: 0,
+            network,
+            session: new_session(),
+            network_status: String::new(),
+        }
+    }
+
+    pub fn get_settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    /// Starts the first computer move and listening to the network
+    pub fn start(&mut self) -> Command<Message> {
+        Command::batch([self.start_computer_move(), self.listen()])
+    }
+
+    fn listen(&self) -> Command<Message> {
+        self.network
+            .as_ref()
+            .map_or_else(Command::none, |network| network.listen(self.session))
+    }
+
+    fn is_client(&self) -> bool {
+        self.network.as_ref().is_some_and(Network::is_client)
+    }
+
+    /// Undo and redo would take back the moves of players at other instances
+    /// without asking them, so they are only allowed in games off the network
+    fn can_take_back(&self) -> bool {
+        self.network.is_none()
+    }
+
+    /// Whether the player to move is a person at this instance
+    fn is_local_turn(&self) -> bool {
+        let GameState::PlayerMove(player) = self.game.get_gamestate() else {
+            return false;
+        };
+        match &self.network {
+            Some(Network::Client { client, seat, .. }) => client.is_some() && *seat == Some(player),
+            _ => self.game.get_players()[player] == PlayerType::User,
+        }
+    }
+
+    /// Starts working out the next computer move in the background, if it is a
+    /// computer player's turn
+    pub fn start_computer_move(&mut self) -> Command<Message> {
+        let player = self.game.get_current_player();
+        match self.game.get_waiting_bot() {
+            // The host plays the bots of a joined game
+            Some(bot) if !self.thinking && !self.is_client() && !self.crashed.contains(&player) => {
+                self.thinking = true;
+                let generation = self.generation;
+                let engine = self.engines[player].clone();
+                if let (PlayerType::External(_), None) = (self.game.get_players()[player], &engine)
+                {
+                    self.engine_status = engine_failure(player, &ExternalEngineError::NoProgram);
+                }
+                Command::perform(
+                    think(self.game.clone(), bot, engine, self.delay),
+                    move |result| match result {
+                        Some((point, error)) => {
+                            GameMessage::ComputerMove(generation, point, error).into()
+                        }
+                        None => GameMessage::BotCrashed(generation, player).into(),
+                    },
+                )
+            }
+            _ => Command::none(),
+        }
+    }
+
+    /// Adds the result to the ratings the first time the game finishes
+    fn rate_finished_game(&mut self) {
+        let state = self.game.get_gamestate();
+        if self.rated || matches!(state, GameState::PlayerMove(_)) {
+            return;
+        }
+        self.rated = true;
+
+        let result = Ratings::load(RATINGS_PATH).and_then(|mut ratings| {
+            let before: Vec<_> = self
+                .competitors
+                .iter()
+                .map(|competitor| {
+                    ratings
+                        .get(competitor)
+                        .map_or(DEFAULT_RATING, |rating| rating.rating)
+                })
+                .collect();
+            ratings.record_game(&self.competitors, &state);
+            ratings.save(RATINGS_PATH)?;
+            Ok(self
+                .competitors
+                .iter()
+                .zip(before)
+                .filter_map(|(competitor, before)| {
+                    let after = ratings.get(competitor)?.rating;
+                    Some(format!("{competitor}: {after:.0} ({:+.0})", after - before))
+                })
+                .collect::<Vec<_>>()
+                .join(", "))
+        });
+        self.rating_status = match result {
+            Ok(changes) => changes,
+            Err(error) => error.to_string(),
+        };
+    }
+
+    /// Drops any computer move or hint that is still being worked out
+    fn cancel_computer_move(&mut self) {
+        self.generation += 1;
+        self.thinking = false;
+        self.finding_hint = false;
+    }
+
+    /// Whether the player to move is a person who can ask for a hint right now
+    fn can_hint(&self) -> bool {
+        self.is_local_turn() && !self.thinking && !self.finding_hint && self.hint.is_none()
+    }
+
+    /// Starts working out a hint for the current player in the background,
+    /// counting it against them
+    fn start_hint(&mut self) -> Command<Message> {
+        if !self.can_hint() {
+            return Command::none();
+        }
+        self.game.record_hint(self.game.get_current_player());
+        self.finding_hint = true;
+        let generation = self.generation;
+        let game = self.game.clone();
+        Command::perform(find_hint(game), move |hint| {
+            GameMessage::ShowHint(generation, hint).into()
+        })
+    }
+
+    /// Summary of the hints each player has used, if anyone has used one
+    fn hint_counts(&self) -> Option<String> {
+        let counts = self.game.get_hint_counts();
+        counts.iter().any(|&count| count > 0).then(|| {
+            let counts: Vec<_> = counts
+                .iter()
+                .enumerate()
+                .map(|(player, count)| format!("Player {}: {count}", player + 1))
+                .collect();
+            format!("Hints used: {}", counts.join(", "))
+        })
+    }
+
     pub fn view(&self) -> iced::Element<'_, Message, iced::Renderer<Theme>> {
+        let win = match self.game.get_gamestate() {
+            GameState::PlayerWon(win) => Some(win),
+            _ => None,
+        };
+
+        let scores = self.heatmap_scores();
+        // Scores grow exponentially with the length of a line, so the colors
+        // follow their logarithm to keep the smaller differences visible
+        let max_score = scores
+            .iter()
+            .flatten()
+            .flatten()
+            .flatten()
+            .max()
+            .map_or(0.0, |&score| (score as f32).ln_1p());
+
         let grid = column(
-            self.get_board()
+            self.game
+                .get_board()
                 .into_iter()
                 .enumerate()
                 .map(|(y, row_values)| {
@@ -31,36 +347,56 @@ impl Game {
                         .into_iter()
                         .enumerate()
                         .map(|(x, tile)| {
+                            let point = Point::new(x as isize, y as isize);
+                            let score = scores.as_ref().and_then(|scores| scores[y][x]);
+                            let color = match (tile, score) {
+                                (Some(player), _) => player_color(player),
+                                (None, Some(score)) if max_score > 0.0 => heat(
+                                    player_color(self.game.get_current_player()),
+                                    (score as f32).ln_1p() / max_score,
+                                ),
+                                (None, _) => Color::from_rgb(0.5f32, 0.5f32, 0.5f32),
+                            };
                             let button = button("").width(Length::Fill);
-                            match tile {
-                                Some(player) => {
-                                    button.style(theme::Button::Custom(Box::new(match player {
-                                        0 => ButtonColor(Color::from_rgb(1f32, 0f32, 0f32)), // Red
-                                        1 => ButtonColor(Color::from_rgb(0f32, 1f32, 0f32)), // Green
-                                        2 => ButtonColor(Color::from_rgb(0f32, 0f32, 1f32)), // Blue
-                                        3 => ButtonColor(Color::from_rgb(1f32, 1f32, 0f32)), // Yellow
-                                        4 => ButtonColor(Color::from_rgb(1f32, 0f32, 1f32)), // Magenta
-                                        5 => ButtonColor(Color::from_rgb(0f32, 1f32, 1f32)), // Aqua
-                                        6 => ButtonColor(Color::from_rgb(0.5f32, 0f32, 0f32)), // Maroon
-                                        7 => ButtonColor(Color::from_rgb(0f32, 0.5f32, 0f32)), // Dark Green
-                                        8 => ButtonColor(Color::from_rgb(0f32, 0f32, 0.5f32)), // Dark Blue
-                                        9 => ButtonColor(Color::from_rgb(0f32, 0.5f32, 0.5f32)), // Something
-                                        10 => ButtonColor(Color::from_rgb(0.75f32,0.5,0.5)),
-                                        11 => ButtonColor(Color::from_rgb(0.75f32,0.5,1f32)),
-                                        12 => ButtonColor(Color::from_rgb(0.6, 0.4, 0.2)),
-                                        _ => ButtonColor(Color::from_rgb(1f32, 1f32, 1f32)),
-                                    })))
+                            let hinted = self.hint.is_some_and(|hint| hint.point == point);
+                            let cell = match &win {
+                                Some(win) if win.contains(&point) => button
+                                    .style(theme::Button::Custom(Box::new(HighlightColor(color)))),
+                                Some(_) => button.style(theme::Button::Custom(Box::new(
+                                    ButtonColor(dim(color)),
+                                ))),
+                                None => {
+                                    let button = if hinted {
+                                        button.style(theme::Button::Custom(Box::new(
+                                            HighlightColor(color),
+                                        )))
+                                    } else {
+                                        button.style(theme::Button::Custom(Box::new(ButtonColor(
+                                            color,
+                                        ))))
+                                    };
+                                    // Spectators can only watch
+                                    let busy = self.thinking
+                                        || self.finding_hint
+                                        || self.network.as_ref().is_some_and(Network::is_spectator);
+                                    match (tile, busy, self.game.get_landing_point(&point)) {
+                                        (None, false, Ok(point)) => {
+                                            button.on_press(GameMessage::PlayMove(point).into())
+                                        }
+                                        _ => button,
+                                    }
                                 }
-                                None => button
-                                    .style(theme::Button::Custom(Box::new(ButtonColor(
-                                        Color::from_rgb(0.5f32, 0.5f32, 0.5f32),
-                                    ))))
-                                    .on_press(
-                                        GameMessage::PlayMove(Point::new(x as isize, y as isize))
-                                            .into(),
-                                    ),
+                            };
+                            match score {
+                                Some(score) => tooltip(
+                                    cell,
+                                    score,
+                                    iced::widget::tooltip::Position::FollowCursor,
+                                )
+                                .style(theme::Container::Box)
+                                .into(),
+                                None => cell.into(),
                             }
-                            .into()
                         })
                         .collect())
                     .height(Length::Fill)
@@ -72,7 +408,82 @@ impl Game {
         .spacing(1)
         .into();
 
-        let content = column(vec![grid]);
+        let controls = row(vec![
+            {
+                let button = button("Undo").style(theme::Button::Text);
+                if self.game.can_undo() && self.can_take_back() {
+                    button.on_press(GameMessage::Undo.into())
+                } else {
+                    button
+                }
+            }
+            .into(),
+            {
+                let button = button("Redo").style(theme::Button::Text);
+                if self.game.can_redo() && self.can_take_back() {
+                    button.on_press(GameMessage::Redo.into())
+                } else {
+                    button
+                }
+            }
+            .into(),
+            {
+                let button = button("Hint").style(theme::Button::Text);
+                if self.can_hint() {
+                    button.on_press(GameMessage::Hint.into())
+                } else {
+                    button
+                }
+            }
+            .into(),
+            text(self.hint_text()).into(),
+            text_input("Save file", &self.save_path)
+                .on_input(|value| GameMessage::SetSavePath(value).into())
+                .width(Length::Fixed(200.0))
+                .into(),
+            button("Save")
+                .on_press(GameMessage::Save.into())
+                .style(theme::Button::Text)
+                .into(),
+            text(&self.save_status).into(),
+            Space::new(Length::Fill, 0).into(),
+            text(format!("Seed: {}", self.game.get_seed())).into(),
+            text(if self.thinking {
+                format!("Player {} is thinking…", self.game.get_current_player() + 1)
+            } else {
+                String::new()
+            })
+            .into(),
+        ])
+        .spacing(10)
+        .into();
+
+        let heatmap_controls = row(vec![
+            checkbox("Heatmap", self.heatmap, |value| {
+                GameMessage::SetHeatmap(value).into()
+            })
+            .into(),
+            if self.heatmap {
+                pick_list(
+                    self.heatmap_choices(),
+                    Some(self.heatmap_weights.clone()),
+                    |value| GameMessage::SetHeatmapWeights(value).into(),
+                )
+                .into()
+            } else {
+                Space::new(0, 0).into()
+            },
+            text(&self.engine_status).into(),
+        ])
+        .align_items(iced::Alignment::Center)
+        .spacing(10)
+        .into();
+
+        let mut rows = vec![controls, heatmap_controls];
+        rows.extend(self.network_controls());
+        rows.extend(self.game_over_panel());
+        rows.push(grid);
+        let content = column(rows).spacing(10);
 
         container(content)
             .width(Length::Fill)
@@ -82,31 +493,493 @@ impl Game {
             .into()
     }
 
-    pub fn handle_message(&mut self, message: Message) {
+    /// Every choice of weights for the heatmap, with the profiles last
+    fn heatmap_choices(&self) -> Vec<HeatmapWeights> {
+        let mut choices = vec![HeatmapWeights::CurrentPlayer];
+        choices.extend(Difficulty::ALL.iter().flat_map(|&difficulty| {
+            Strategy::ALL
+                .iter()
+                .map(move |&strategy| HeatmapWeights::Bot(difficulty, strategy))
+        }));
+        choices.extend(
+            self.settings
+                .get_profiles()
+                .get_names()
+                .into_iter()
+                .map(HeatmapWeights::Profile),
+        );
+        choices
+    }
+
+    fn get_heatmap_weights(&self) -> ComputerWeights {
+        let player = self.game.get_current_player();
+        let own_weights = || {
+            let bot = self.game.get_players()[player]
+                .get_bot()
+                .copied()
+                .unwrap_or_default();
+            self.game.get_weights(player, bot)
+        };
+        match &self.heatmap_weights {
+            HeatmapWeights::CurrentPlayer => own_weights(),
+            HeatmapWeights::Bot(difficulty, strategy) => {
+                self.settings.get_weight_table().get(*difficulty, *strategy)
+            }
+            HeatmapWeights::Profile(name) => self
+                .settings
+                .get_profiles()
+                .get(name)
+                .map_or_else(own_weights, |profile| profile.weights),
+        }
+    }
+
+    /// The scores of the empty cells for the player to move, while the heatmap
+    /// is on and the game is still going
+    fn heatmap_scores(&self) -> Option<Vec<Vec<Option<u64>>>> {
+        match self.game.get_gamestate() {
+            GameState::PlayerMove(player) if self.heatmap => Some(
+                self.game
+                    .get_location_scores(player, &self.get_heatmap_weights()),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Where the hint is and why, or that one is being worked out
+    fn hint_text(&self) -> String {
+        match (&self.hint, self.finding_hint) {
+            (_, true) => String::from("Finding a hint…"),
+            (Some(hint), false) => {
+                let place = match self.game.get_placement() {
+                    Placement::Gravity => format!("Column {}", hint.point.x + 1),
+                    Placement::Free => {
+                        format!("Column {}, row {}", hint.point.x + 1, hint.point.y + 1)
+                    }
+                };
+                match hint.reason {
+                    Some(reason) => format!("{place}: {reason}"),
+                    None => place,
+                }
+            }
+            (None, false) => String::new(),
+        }
+    }
+
+    /// Announces the result, with the options for what to do next
+    fn game_over_panel(&self) -> Option<iced::Element<'_, Message, iced::Renderer<Theme>>> {
+        let result = match self.game.get_gamestate() {
+            GameState::PlayerMove(_) => return None,
+            GameState::PlayerWon(win) => {
+                text(format!("Player {} wins!", win.player + 1)).style(player_color(win.player))
+            }
+            GameState::Draw => text("It's a draw!"),
+        };
+
+        Some(
+            column(vec![
+                result.size(40).into(),
+                text(&self.rating_status).into(),
+                text(self.hint_counts().unwrap_or_default()).into(),
+                row(vec![
+                    if self.is_client() {
+                        text("The host can start a rematch").into()
+                    } else {
+                        row(vec![
+                            button("Rematch")
+                                .on_press(GameMessage::Rematch.into())
+                                .into(),
+                            checkbox("Rotate who moves first", self.rotate_players, |value| {
+                                GameMessage::SetRotatePlayers(value).into()
+                            })
+                            .into(),
+                        ])
+                        .align_items(iced::Alignment::Center)
+                        .spacing(20)
+                        .into()
+                    },
+                    button("Change Settings")
+                        .on_press(Message::ChangeSettings)
+                        .into(),
+                    button("Quit").on_press(Message::Quit).into(),
+                ])
+                .align_items(iced::Alignment::Center)
+                .spacing(20)
+                .into(),
+            ])
+            .align_items(iced::Alignment::Center)
+            .width(Length::Fill)
+            .spacing(10)
+            .into(),
+        )
+    }
+
+    pub fn handle_message(&mut self, message: Message) -> Command<Message> {
+        let mut command = Command::none();
         if let Message::GameMessage(message) = message {
             match message {
-                GameMessage::PlayMove(point) => {
-                    self.play_move(point);
+                GameMessage::PlayMove(point) => match &self.network {
+                    _ if !self.is_local_turn() => {}
+                    Some(Network::Client {
+                        client: Some(client),
+                        ..
+                    }) => {
+                        // The move shows once the host sends the game back
+                        if let Err(error) = client.send_move(point) {
+                            self.network_status = error.to_string();
+                        }
+                    }
+                    _ => {
+                        if self.game.play_move(point).is_ok() {
+                            self.hint = None;
+                        }
+                    }
+                },
+                GameMessage::HostEvent(session, event) => {
+                    if session == self.session {
+                        command = self.handle_host_event(event);
+                    }
+                }
+                GameMessage::FromHost(session, message) => {
+                    if session == self.session {
+                        command = self.handle_host_message(message);
+                    }
+                }
+                GameMessage::Reconnect => command = self.reconnect(),
+                GameMessage::ComputerMove(generation, point, error) => {
+                    if generation == self.generation {
+                        self.thinking = false;
+                        if let Some(error) = error {
+                            self.engine_status = error;
+                        }
+                        if let Some(point) = point {
+                            self.game.play_computer_move(point).ok();
+                        }
+                    }
+                }
+                GameMessage::BotCrashed(generation, player) => {
+                    if generation == self.generation {
+                        self.thinking = false;
+                        self.crashed.push(player);
+                        self.engine_status = format!(
+                            "Player {}'s bot crashed, so it won't move again until the rematch",
+                            player + 1
+                        );
+                    }
+                }
+                GameMessage::Hint => return self.start_hint(),
+                GameMessage::ShowHint(generation, hint) => {
+                    if generation == self.generation {
+                        self.finding_hint = false;
+                        self.hint = hint;
+                    }
+                }
+                GameMessage::SetHeatmap(value) => {
+                    self.heatmap = value;
+                }
+                GameMessage::SetHeatmapWeights(weights) => {
+                    self.heatmap_weights = weights;
+                }
+                GameMessage::Undo | GameMessage::Redo if !self.can_take_back() => {}
+                GameMessage::Rematch if self.is_client() => {}
+                GameMessage::Undo => {
+                    self.cancel_computer_move();
+                    self.hint = None;
+                    self.game.undo();
+                }
+                GameMessage::Redo => {
+                    self.cancel_computer_move();
+                    self.hint = None;
+                    self.game.redo();
+                }
+                GameMessage::SetSavePath(path) => {
+                    self.save_path = path;
                 }
+                GameMessage::SetRotatePlayers(value) => {
+                    self.rotate_players = value;
+                }
+                GameMessage::Rematch => {
+                    self.cancel_computer_move();
+                    self.game = self.game.rematch(self.rotate_players);
+                    if self.rotate_players {
+                        self.competitors.rotate_left(1);
+                        self.engines.rotate_left(1);
+                        if let Some(Network::Host(host)) = &mut self.network {
+                            host.rotate_seats(&self.game);
+                            self.present = host.get_present(&self.game);
+                        }
+                    }
+                    self.rated = false;
+                    self.crashed.clear();
+                    self.hint = None;
+                    self.rating_status.clear();
+                    self.save_status.clear();
+                    self.engine_status.clear();
+                }
+                GameMessage::Save => {
+                    self.save_status = match self.game.save(&self.save_path) {
+                        Ok(()) => String::from("Saved"),
+                        Err(error) => error.to_string(),
+                    };
+                }
+            }
+        }
+        if let Some(Network::Host(host)) = &mut self.network {
+            host.sync(&self.game);
+        }
+        self.rate_finished_game();
+        Command::batch([command, self.start_computer_move()])
+    }
+
+    /// Acts on something that happened on a hosted game's connections, and
+    /// listens for the next
+    fn handle_host_event(&mut self, event: Option<HostEvent>) -> Command<Message> {
+        let Some(Network::Host(host)) = &mut self.network else {
+            return Command::none();
+        };
+        let Some(event) = event else {
+            self.network_status = String::from("Stopped hosting");
+            return Command::none();
+        };
+        match host.handle(event, &mut self.game) {
+            HostUpdate::None => {}
+            HostUpdate::Joined(seat) => {
+                self.network_status = format!("Player {} joined", seat + 1);
+            }
+            HostUpdate::Left(seat) => {
+                self.network_status =
+                    format!("Player {} left, waiting for them to rejoin", seat + 1);
+            }
+            HostUpdate::Moved(_) => self.hint = None,
+            HostUpdate::Spectators(count) => self.spectators = count,
+        }
+        self.present = host.get_present(&self.game);
+        self.listen()
+    }
+
+    /// Acts on a message from the host of a joined game, and listens for the
+    /// next while still connected
+    fn handle_host_message(&mut self, message: Option<HostMessage>) -> Command<Message> {
+        let Some(Network::Client { client, seat, .. }) = &mut self.network else {
+            return Command::none();
+        };
+        match message {
+            Some(HostMessage::Welcome { seat: new_seat }) => {
+                *seat = Some(new_seat);
+                self.network_status = format!("You are player {}", new_seat + 1);
+            }
+            Some(HostMessage::Sync(save)) => match Game::try_from(save) {
+                Ok(mut game) => {
+                    game.set_weight_table(self.game.get_weight_table().clone());
+                    if game.get_history() != self.game.get_history() {
+                        self.cancel_computer_move();
+                        self.hint = None;
+                    }
+                    self.game = game;
+                }
+                Err(error) => {
+                    self.network_status = format!("The host sent an invalid game: {error}");
+                }
+            },
+            Some(HostMessage::Present(present)) => self.present = present,
+            Some(HostMessage::Spectators(count)) => self.spectators = count,
+            Some(HostMessage::Rejected(reason)) | Some(HostMessage::Closed(reason)) => {
+                self.network_status = reason;
+            }
+            None => {
+                *client = None;
+                if self.network_status.is_empty() {
+                    self.network_status = String::from("Lost the connection to the host");
+                }
+                return Command::none();
+            }
+        }
+        self.listen()
+    }
+
+    /// Joins the game again after losing the connection, taking back the same
+    /// seat or spectating again
+    fn reconnect(&mut self) -> Command<Message> {
+        let Some(Network::Client {
+            client: client @ None,
+            address,
+            seat,
+        }) = &mut self.network
+        else {
+            return Command::none();
+        };
+        let result = match seat {
+            Some(seat) => GameClient::join(*address, Some(*seat))
+                .map(|(client, seat, game)| (client, Some(seat), game)),
+            None => GameClient::spectate(*address).map(|(client, game)| (client, None, game)),
+        };
+        match result {
+            Ok((new_client, new_seat, mut game)) => {
+                *client = Some(new_client);
+                *seat = new_seat;
+                game.set_weight_table(self.game.get_weight_table().clone());
+                self.game = game;
+                self.hint = None;
+                self.cancel_computer_move();
+                self.session = new_session();
+                self.network_status = String::from("Reconnected");
+                self.listen()
+            }
+            Err(error) => {
+                self.network_status = error.to_string();
+                Command::none()
             }
         }
     }
+
+    /// Who is connected, and a way back in after losing the connection, for
+    /// games played over the network
+    fn network_controls(&self) -> Option<iced::Element<'_, Message, iced::Renderer<Theme>>> {
+        let (summary, reconnect) = match self.network.as_ref()? {
+            Network::Host(host) => (format!("Hosting on port {}", host.get_port()), false),
+            Network::Client {
+                client: Some(_),
+                address,
+                seat: Some(seat),
+            } => (
+                format!("Playing as player {} on {address}", seat + 1),
+                false,
+            ),
+            Network::Client {
+                client: Some(_),
+                address,
+                seat: None,
+            } => (format!("Watching the game on {address}"), false),
+            Network::Client {
+                client: None,
+                address,
+                ..
+            } => (format!("Disconnected from {address}"), true),
+        };
+        let away: Vec<_> = self
+            .present
+            .iter()
+            .enumerate()
+            .filter(|(_, present)| !**present)
+            .map(|(player, _)| format!("player {}", player + 1))
+            .collect();
+
+        let mut items = vec![text(summary).into()];
+        if !away.is_empty() {
+            items.push(text(format!("Waiting for {}", away.join(", "))).into());
+        }
+        match self.spectators {
+            0 => {}
+            1 => items.push(text("1 spectator").into()),
+            count => items.push(text(format!("{count} spectators")).into()),
+        }
+        items.push(text(&self.network_status).into());
+        if reconnect {
+            items.push(
+                button("Reconnect")
+                    .on_press(GameMessage::Reconnect.into())
+                    .into(),
+            );
+        }
+        Some(
+            row(items)
+                .align_items(iced::Alignment::Center)
+                .spacing(20)
+                .into(),
+        )
+    }
+}
+
+/// The program of each external player with an engine command. Each is only
+/// started when its player first has to move.
+fn start_engines(game: &Game) -> Vec<Option<Arc<Mutex<ExternalEngine>>>> {
+    (0..game.get_player_count())
+        .map(|player| {
+            game.get_player_engine(player)
+                .map(|command| Arc::new(Mutex::new(ExternalEngine::new(command.clone()))))
+        })
+        .collect()
+}
+
+fn engine_failure(player: Player, error: &ExternalEngineError) -> String {
+    format!(
+        "Player {}'s engine failed, so its bot moved instead: {error}",
+        player + 1
+    )
+}
+
+/// Works out a computer move on its own thread, so the window keeps responding.
+/// With an engine, the engine is asked and `bot` only moves if it fails, in
+/// which case the failure is returned too. Returns `None` if the thread panicked.
+async fn think(
+    game: Game,
+    bot: Bot,
+    engine: Option<Arc<Mutex<ExternalEngine>>>,
+    delay: Duration,
+) -> Option<(Option<Point>, Option<String>)> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let result = match engine {
+            Some(engine) => {
+                let mut engine = engine.lock().unwrap_or_else(PoisonError::into_inner);
+                let (point, error) = game.get_external_move(&mut engine, bot);
+                let player = game.get_current_player();
+                (point, error.map(|error| engine_failure(player, &error)))
+            }
+            None => (game.get_computer_move(bot), None),
+        };
+        if let Some(remaining) = delay.checked_sub(start.elapsed()) {
+            thread::sleep(remaining);
+        }
+        sender.send(result).ok();
+    });
+    receiver.await.ok()
 }
+
+/// Works out a hint on its own thread, so the window keeps responding
+async fn find_hint(game: Game) -> Option<Hint> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        sender.send(game.get_hint()).ok();
+    });
+    receiver.await.ok().flatten()
+}
+
+fn player_color(player: Player) -> Color {
+    let [r, g, b] = PLAYER_COLORS.get(player).copied().unwrap_or([255; 3]);
+    Color::from_rgb8(r, g, b)
+}
+
+/// Blends the empty cell color towards `color` by `amount`, from 0 to 1
+fn heat(color: Color, amount: f32) -> Color {
+    let blend = |channel: f32| 0.5 + (channel - 0.5) * amount;
+    Color::from_rgb(blend(color.r), blend(color.g), blend(color.b))
+}
+
+/// Blends a color towards the background so the winning line stands out
+fn dim(color: Color) -> Color {
+    Color::from_rgb(
+        color.r * 0.3 + 0.35,
+        color.g * 0.3 + 0.35,
+        color.b * 0.3 + 0.35,
+    )
+}
+
 struct ButtonColor(iced::Color);
 
 impl button::StyleSheet for ButtonColor {
-    fn active(&self, style: &Self::Style) -> button::Appearance {
+    fn active(&self, _style: &Self::Style) -> button::Appearance {
         button::Appearance {
             background: Some(iced::Background::Color(self.0)),
-            border_color: Color::from_rgb(0f32,0f32,0f32),
+            border_color: Color::from_rgb(0f32, 0f32, 0f32),
             ..Default::default()
         }
     }
 
-    fn disabled(&self, style: &Self::Style) -> button::Appearance {
+    fn disabled(&self, _style: &Self::Style) -> button::Appearance {
         button::Appearance {
             background: Some(iced::Background::Color(self.0)),
-            border_color: Color::from_rgb(0f32,0f32,0f32),
+            border_color: Color::from_rgb(0f32, 0f32, 0f32),
             border_width: 1f32,
             ..Default::default()
         }
@@ -114,3 +987,18 @@ impl button::StyleSheet for ButtonColor {
 
     type Style = Theme;
 }
+
+struct HighlightColor(iced::Color);
+
+impl button::StyleSheet for HighlightColor {
+    fn active(&self, _style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(iced::Background::Color(self.0)),
+            border_color: Color::from_rgb(1f32, 1f32, 1f32),
+            border_width: 4f32,
+            ..Default::default()
+        }
+    }
+
+    type Style = Theme;
+}
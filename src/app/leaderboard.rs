@@ -0,0 +1,109 @@
+use iced::{
+    widget::{button, column, container, row, scrollable, text, Space},
+    Element, Length, Renderer, Theme,
+};
+
+use four_in_a_row_engine::Ratings;
+
+use crate::Message;
+
+use super::{GameSettings, RATINGS_PATH};
+
+pub struct Leaderboard {
+    /// The ratings as they were when the screen was opened, or why they could
+    /// not be read
+    ratings: Result<Ratings, String>,
+    /// The settings to go back to
+    settings: GameSettings,
+}
+
+impl Leaderboard {
+    pub fn new(settings: GameSettings) -> Self {
+        Self {
+            ratings: Ratings::load(RATINGS_PATH).map_err(|error| error.to_string()),
+            settings,
+        }
+    }
+
+    pub fn get_settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    pub fn view(&self) -> iced::Element<'_, Message, iced::Renderer<Theme>> {
+        let title = text("Leaderboard").size(50).into();
+
+        let body = match &self.ratings {
+            Ok(ratings) if ratings.get_leaderboard().is_empty() => {
+                text("Finish a game to get a rating").into()
+            }
+            Ok(ratings) => {
+                let mut rows = vec![table_row(
+                    ["#", "Player", "Rating", "Games", "Won", "Drawn", "Lost"].map(String::from),
+                )];
+                rows.extend(
+                    ratings
+                        .get_leaderboard()
+                        .iter()
+                        .enumerate()
+                        .map(|(rank, rating)| {
+                            table_row([
+                                (rank + 1).to_string(),
+                                rating.competitor.to_string(),
+                                format!("{:.0}", rating.rating),
+                                rating.games.to_string(),
+                                rating.wins.to_string(),
+                                rating.draws.to_string(),
+                                rating.losses.to_string(),
+                            ])
+                        }),
+                );
+
+                let difficulties = ratings
+                    .get_difficulty_ratings()
+                    .into_iter()
+                    .map(|(difficulty, rating)| text(format!("{difficulty}: {rating:.0}")).into())
+                    .collect();
+
+                column(vec![
+                    scrollable(column(rows).spacing(5))
+                        .height(Length::Fill)
+                        .into(),
+                    text("Average bot rating by difficulty").size(30).into(),
+                    row(difficulties).spacing(30).into(),
+                ])
+                .align_items(iced::Alignment::Center)
+                .spacing(10)
+                .into()
+            }
+            Err(error) => text(error).into(),
+        };
+
+        let back = button(text("Back").size(30))
+            .on_press(Message::ChangeSettings)
+            .into();
+
+        let content = column(vec![title, body, Space::new(0, 10).into(), back])
+            .align_items(iced::Alignment::Center)
+            .width(Length::Fill)
+            .padding(20)
+            .spacing(10);
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .into()
+    }
+}
+
+/// Width of each column in the table, from rank to losses
+const COLUMN_WIDTHS: [f32; 7] = [40.0, 300.0, 80.0, 80.0, 80.0, 80.0, 80.0];
+
+fn table_row<'a>(cells: [String; 7]) -> Element<'a, Message, Renderer> {
+    row(cells
+        .into_iter()
+        .zip(COLUMN_WIDTHS)
+        .map(|(cell, width)| text(cell).width(Length::Fixed(width)).into())
+        .collect())
+    .into()
+}
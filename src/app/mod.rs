@@ -1,5 +1,8 @@
-
 mod game;
 pub use game::*;
+mod leaderboard;
+pub use leaderboard::*;
+mod network;
+pub use network::*;
 mod settings;
 pub use settings::*;
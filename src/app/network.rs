@@ -0,0 +1,86 @@
+use std::{
+    net::SocketAddr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use iced::{futures::channel::oneshot, Command};
+
+use four_in_a_row_engine::{EventQueue, GameClient, GameHost, Player};
+
+use crate::Message;
+
+use super::GameMessage;
+
+/// Numbers each connection, so events from one that has been replaced are
+/// told apart and ignored
+static NEXT_SESSION: AtomicUsize = AtomicUsize::new(0);
+
+pub fn new_session() -> usize {
+    NEXT_SESSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The other instances a game is played with
+pub enum Network {
+    /// This instance serves the game to its remote players
+    Host(GameHost),
+    /// This instance plays one seat of a game hosted elsewhere, or follows it
+    /// as a spectator when it has no seat. The client is `None` while
+    /// disconnected.
+    Client {
+        client: Option<GameClient>,
+        address: SocketAddr,
+        seat: Option<Player>,
+    },
+}
+
+impl Network {
+    pub fn joined(client: GameClient, seat: Player) -> Self {
+        Self::Client {
+            address: client.get_address(),
+            client: Some(client),
+            seat: Some(seat),
+        }
+    }
+
+    pub fn spectating(client: GameClient) -> Self {
+        Self::Client {
+            address: client.get_address(),
+            client: Some(client),
+            seat: None,
+        }
+    }
+
+    pub fn is_client(&self) -> bool {
+        matches!(self, Self::Client { .. })
+    }
+
+    pub fn is_spectator(&self) -> bool {
+        matches!(self, Self::Client { seat: None, .. })
+    }
+
+    /// Waits in the background for the next thing to happen on the connection
+    pub fn listen(&self, session: usize) -> Command<Message> {
+        match self {
+            Self::Host(host) => Command::perform(next_event(host.events()), move |event| {
+                GameMessage::HostEvent(session, event).into()
+            }),
+            Self::Client {
+                client: Some(client),
+                ..
+            } => Command::perform(next_event(client.events()), move |message| {
+                GameMessage::FromHost(session, message).into()
+            }),
+            Self::Client { client: None, .. } => Command::none(),
+        }
+    }
+}
+
+/// Waits for the next event on its own thread, so the window keeps responding
+async fn next_event<T: Send + 'static>(events: EventQueue<T>) -> Option<T> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        sender.send(events.next()).ok();
+    });
+    receiver.await.ok().flatten()
+}
@@ -1,20 +1,73 @@
+use std::{fmt::Display, sync::Arc, time::Duration};
+
 use iced::{
     theme,
     widget::{button, column, container, pick_list, radio, row, text, text_input, Space},
-    Element, Length, Renderer, Settings, Theme,
+    Color, Element, Length, Renderer, Theme,
 };
 
-use crate::{
-    game::{Bot, Difficulty, Game, PlayerType, Strategy},
-    Message,
+use four_in_a_row_engine::{
+    Bot, Competitor, Difficulty, Engine, EngineCommand, Game, GameClient, GameHost, NewGameError,
+    Placement, Player, PlayerType, Profiles, Strategy, WeightTable, DEFAULT_PORT, MAX_BOARD_SIZE,
 };
 
+use crate::Message;
+
+pub const DEFAULT_SAVE_PATH: &str = "four-in-a-row.json";
+pub const RATINGS_PATH: &str = "ratings.json";
+/// Bot weights, such as those written by the tuner, replacing the built-in ones
+pub const WEIGHTS_PATH: &str = "weights.json";
+/// Named bot profiles to choose from alongside the built-in bots
+pub const PROFILES_PATH: &str = "profiles.json";
+/// Everything said to and by external engines is added to this file
+pub const ENGINE_LOG_PATH: &str = "engines.log";
+
 #[derive(Clone)]
 pub struct GameSettings {
     width: isize,
     height: isize,
     goal: isize,
     players: Vec<PlayerType>,
+    /// Profile names of the people playing, by player. Blank names fall back to
+    /// the player's number.
+    names: Vec<String>,
+    placement: Placement,
+    /// Minimum time in milliseconds each computer move takes
+    delay: u64,
+    /// Seed for the computer players, or a random one each game when `None`
+    seed: Option<u64>,
+    save_path: String,
+    load_error: Option<String>,
+    weight_table: Arc<WeightTable>,
+    weights_error: Option<String>,
+    profiles: Profiles,
+    profiles_error: Option<String>,
+    /// The custom profile chosen for each player's bot, if any
+    player_profiles: Vec<Option<String>>,
+    /// The command line starting each external player's engine, by player
+    engine_commands: Vec<String>,
+    /// The port remote players join on when this instance hosts
+    port: u16,
+    /// The host to join, as an address and port
+    join_address: String,
+    /// Why hosting or joining last failed
+    network_error: Option<String>,
+}
+
+/// A choice in the profile list of a player row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileChoice {
+    BuiltIn,
+    Custom(String),
+}
+
+impl Display for ProfileChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BuiltIn => f.write_str("Built-in"),
+            Self::Custom(name) => f.write_str(name),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +81,17 @@ pub enum SettingsMessage {
     RemovePlayer(usize),
     AddPlayer,
     SetPlayerType(usize, PlayerType),
+    SetName(usize, String),
+    SetEngineCommand(usize, String),
+    SetProfile(usize, ProfileChoice),
+    ReloadBotFiles,
+    SetPlacement(Placement),
+    SetDelay(u64),
+    ParseDelay(String),
+    ParseSeed(String),
+    SetSavePath(String),
+    ParsePort(String),
+    SetJoinAddress(String),
 }
 
 impl From<SettingsMessage> for Message {
@@ -43,16 +107,216 @@ impl Default for GameSettings {
             height: 6,
             goal: 4,
             players: vec![PlayerType::User, PlayerType::Computer(Bot::default())],
+            names: vec![String::new(); 2],
+            placement: Placement::default(),
+            delay: 0,
+            seed: None,
+            save_path: String::from(DEFAULT_SAVE_PATH),
+            load_error: None,
+            weight_table: Arc::default(),
+            weights_error: None,
+            profiles: Profiles::default(),
+            profiles_error: None,
+            player_profiles: vec![None; 2],
+            engine_commands: vec![String::new(); 2],
+            port: DEFAULT_PORT,
+            join_address: format!("127.0.0.1:{DEFAULT_PORT}"),
+            network_error: None,
         }
     }
 }
 
 impl GameSettings {
-    pub fn to_game(&self) -> Game {
-        Game::new(self.width, self.height, self.goal, self.players.clone()).unwrap()
+    pub fn to_game(&self) -> Result<Game, NewGameError> {
+        let mut game = Game::new(
+            self.width,
+            self.height,
+            self.goal,
+            self.players.clone(),
+            self.placement,
+        )?;
+        if let Some(seed) = self.seed {
+            game.set_seed(seed);
+        }
+        game.set_weight_table(self.weight_table.clone());
+        for (player, profile) in self.player_profiles.iter().enumerate() {
+            let profile = profile.as_deref().and_then(|name| self.profiles.get(name));
+            game.set_player_profile(player, profile.cloned());
+        }
+        self.set_engines(&mut game);
+        Ok(game)
+    }
+
+    /// Gives each external player of `game` the engine command typed in for
+    /// its seat. Saves never carry engine commands, so loaded games get theirs
+    /// from here too, and seats without one are played by their bot.
+    fn set_engines(&self, game: &mut Game) {
+        for player in 0..game.get_players().len() {
+            if let PlayerType::External(_) = game.get_players()[player] {
+                let command = self
+                    .engine_commands
+                    .get(player)
+                    .and_then(|command| command.parse().ok())
+                    .map(|command| EngineCommand {
+                        log: Some(ENGINE_LOG_PATH.into()),
+                        ..command
+                    });
+                game.set_player_engine(player, command);
+            }
+        }
+    }
+
+    /// Reads the bot weights from [`WEIGHTS_PATH`] and the profiles from
+    /// [`PROFILES_PATH`]. If either file is invalid, the built-in weights or no
+    /// profiles are used instead, and the error is kept to show.
+    pub fn load_bot_files(&mut self) {
+        match WeightTable::load(WEIGHTS_PATH) {
+            Ok(table) => {
+                self.weight_table = Arc::new(table);
+                self.weights_error = None;
+            }
+            Err(error) => {
+                self.weight_table = Arc::default();
+                self.weights_error = Some(error.to_string());
+            }
+        }
+
+        match Profiles::load(PROFILES_PATH) {
+            Ok(profiles) => {
+                self.profiles = profiles;
+                self.profiles_error = None;
+            }
+            Err(error) => {
+                self.profiles = Profiles::default();
+                self.profiles_error = Some(error.to_string());
+            }
+        }
+        for profile in &mut self.player_profiles {
+            if profile
+                .as_deref()
+                .is_some_and(|name| self.profiles.get(name).is_none())
+            {
+                *profile = None;
+            }
+        }
+    }
+
+    pub fn get_errors(&self) -> Vec<NewGameError> {
+        let mut errors = Game::validate(self.width, self.height, self.goal, self.players.len());
+        errors.extend(Game::validate_players(&self.players));
+        errors
+    }
+
+    /// Who is playing each seat of `game`, for the ratings. People are told
+    /// apart by their profile names, and bots by their configuration or custom
+    /// profile.
+    pub fn get_competitors(&self, game: &Game) -> Vec<Competitor> {
+        game.get_players()
+            .iter()
+            .enumerate()
+            .map(|(i, player)| match (player, game.get_player_profile(i)) {
+                (PlayerType::User | PlayerType::Remote, _) => Competitor::Person(self.get_name(i)),
+                (PlayerType::Computer(bot), Some(profile)) => {
+                    Competitor::Profile(profile.name.clone(), bot.get_engine())
+                }
+                (PlayerType::Computer(bot), None) => Competitor::Computer(*bot),
+                (PlayerType::External(bot), _) => game
+                    .get_player_engine(i)
+                    .map_or(Competitor::Computer(*bot), |command| {
+                        Competitor::External(command.to_string())
+                    }),
+            })
+            .collect()
+    }
+
+    fn get_name(&self, player: usize) -> String {
+        match self.names.get(player).map(|name| name.trim()) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => format!("Player {}", player + 1),
+        }
+    }
+
+    pub fn get_weight_table(&self) -> &WeightTable {
+        &self.weight_table
+    }
+
+    pub fn get_profiles(&self) -> &Profiles {
+        &self.profiles
+    }
+
+    pub fn get_delay(&self) -> Duration {
+        Duration::from_millis(self.delay)
+    }
+
+    pub fn get_save_path(&self) -> &str {
+        &self.save_path
+    }
+
+    /// Loads the game at the save path, keeping the error to show if it fails
+    pub fn load_game(&mut self) -> Option<Game> {
+        match Game::load(&self.save_path) {
+            Ok(mut game) => {
+                self.load_error = None;
+                game.set_weight_table(self.weight_table.clone());
+                self.set_engines(&mut game);
+                Some(game)
+            }
+            Err(error) => {
+                self.load_error = Some(error.to_string());
+                None
+            }
+        }
+    }
+
+    /// Starts hosting on the chosen port, keeping the error to show if it fails
+    pub fn start_host(&mut self) -> Option<GameHost> {
+        match GameHost::start(self.port) {
+            Ok(host) => {
+                self.network_error = None;
+                Some(host)
+            }
+            Err(error) => {
+                self.network_error = Some(format!("Could not host on port {}: {error}", self.port));
+                None
+            }
+        }
+    }
+
+    /// Joins the game hosted at the join address, keeping the error to show if
+    /// it fails. Returns the connection, the seat taken and the game.
+    pub fn join_game(&mut self) -> Option<(GameClient, Player, Game)> {
+        match GameClient::join(self.join_address.trim(), None) {
+            Ok((client, seat, mut game)) => {
+                self.network_error = None;
+                game.set_weight_table(self.weight_table.clone());
+                Some((client, seat, game))
+            }
+            Err(error) => {
+                self.network_error = Some(error.to_string());
+                None
+            }
+        }
+    }
+
+    /// Connects to the game hosted at the join address to follow it without
+    /// playing, keeping the error to show if it fails
+    pub fn watch_game(&mut self) -> Option<(GameClient, Game)> {
+        match GameClient::spectate(self.join_address.trim()) {
+            Ok((client, mut game)) => {
+                self.network_error = None;
+                game.set_weight_table(self.weight_table.clone());
+                Some((client, game))
+            }
+            Err(error) => {
+                self.network_error = Some(error.to_string());
+                None
+            }
+        }
     }
 
     pub fn view(&self) -> iced::Element<'_, Message, iced::Renderer<Theme>> {
+        let errors = self.get_errors();
+
         let title = text("Game Settings")
             .size(50)
             .horizontal_alignment(iced::alignment::Horizontal::Center)
@@ -66,17 +330,27 @@ impl GameSettings {
                 numerical_input(
                     text_input("", self.width.to_string().as_str())
                         .on_input(|value| SettingsMessage::ParseWidth(value).into()),
-                    if self.width > self.goal {
+                    if self.width > self.goal + 1 {
                         Some(SettingsMessage::SetWidth(self.width - 1).into())
                     } else {
                         None
                     },
-                    Some(SettingsMessage::SetWidth(self.width + 1).into())
-                )
-                .into()
+                    if self.width < MAX_BOARD_SIZE {
+                        Some(SettingsMessage::SetWidth(self.width + 1).into())
+                    } else {
+                        None
+                    }
+                ),
+                error_text(
+                    &errors,
+                    &[
+                        NewGameError::WidthMustBeAtLeast2,
+                        NewGameError::WidthTooLarge,
+                    ]
+                ),
             ])
             .align_items(iced::Alignment::Center)
-            .width(Length::Fixed(100.0)),
+            .width(Length::Fixed(150.0)),
             column(vec![
                 text("Height")
                     .horizontal_alignment(iced::alignment::Horizontal::Center)
@@ -84,17 +358,27 @@ impl GameSettings {
                 numerical_input(
                     text_input("", self.height.to_string().as_str())
                         .on_input(|value| SettingsMessage::ParseHeight(value).into()),
-                    if self.height > self.goal {
+                    if self.height > self.goal + 1 {
                         Some(SettingsMessage::SetHeight(self.height - 1).into())
                     } else {
                         None
                     },
-                    Some(SettingsMessage::SetHeight(self.height + 1).into())
-                )
-                .into()
+                    if self.height < MAX_BOARD_SIZE {
+                        Some(SettingsMessage::SetHeight(self.height + 1).into())
+                    } else {
+                        None
+                    }
+                ),
+                error_text(
+                    &errors,
+                    &[
+                        NewGameError::HeightMustBeAtLeast2,
+                        NewGameError::HeightTooLarge,
+                    ]
+                ),
             ])
             .align_items(iced::Alignment::Center)
-            .width(Length::Fixed(100.0)),
+            .width(Length::Fixed(150.0)),
             column(vec![
                 text("Goal")
                     .horizontal_alignment(iced::alignment::Horizontal::Center)
@@ -102,22 +386,98 @@ impl GameSettings {
                 numerical_input(
                     text_input("", self.goal.to_string().as_str())
                         .on_input(|value| SettingsMessage::ParseGoal(value).into()),
-                    Some(SettingsMessage::SetGoal(self.goal - 1).into()),
-                    if self.goal < isize::min(self.height, self.width) {
+                    if self.goal > 2 {
+                        Some(SettingsMessage::SetGoal(self.goal - 1).into())
+                    } else {
+                        None
+                    },
+                    if self.goal < isize::min(self.height, self.width) - 1 {
                         Some(SettingsMessage::SetGoal(self.goal + 1).into())
                     } else {
                         None
                     }
+                ),
+                error_text(
+                    &errors,
+                    &[
+                        NewGameError::GoalMustBeAtLeast2,
+                        NewGameError::GoalMustBeLessThanWidth,
+                        NewGameError::GoalMustBeLessThanHeight,
+                    ]
+                ),
+            ])
+            .align_items(iced::Alignment::Center)
+            .width(Length::Fixed(150.0)),
+            column(vec![
+                text("Bot Delay (ms)")
+                    .horizontal_alignment(iced::alignment::Horizontal::Center)
+                    .into(),
+                numerical_input(
+                    text_input("", self.delay.to_string().as_str())
+                        .on_input(|value| SettingsMessage::ParseDelay(value).into()),
+                    if self.delay >= 100 {
+                        Some(SettingsMessage::SetDelay(self.delay - 100).into())
+                    } else {
+                        None
+                    },
+                    Some(SettingsMessage::SetDelay(self.delay + 100).into())
                 )
-                .into()
             ])
             .align_items(iced::Alignment::Center)
-            .width(Length::Fixed(100.0))
+            .width(Length::Fixed(150.0)),
+            column(vec![
+                text("Seed")
+                    .horizontal_alignment(iced::alignment::Horizontal::Center)
+                    .into(),
+                text_input(
+                    "Random",
+                    &self.seed.map(|seed| seed.to_string()).unwrap_or_default()
+                )
+                .on_input(|value| SettingsMessage::ParseSeed(value).into())
+                .into(),
+            ])
+            .align_items(iced::Alignment::Center)
+            .width(Length::Fixed(200.0))
         ]
         .spacing(50)
         .into();
 
-        let player_title = text("Players").size(30).into();
+        let placement = row(Placement::ALL
+            .iter()
+            .map(|placement| {
+                radio(
+                    placement.to_string(),
+                    *placement,
+                    Some(self.placement),
+                    |value| SettingsMessage::SetPlacement(value).into(),
+                )
+                .into()
+            })
+            .collect())
+        .spacing(20)
+        .into();
+
+        let player_title = column(vec![
+            text("Players").size(30).into(),
+            error_text(
+                &errors,
+                &[
+                    NewGameError::PlayersMustBeAtLeast2,
+                    NewGameError::EngineNeedsTwoPlayers,
+                ],
+            ),
+        ])
+        .align_items(iced::Alignment::Center)
+        .into();
+
+        let profile_choices: Vec<_> = std::iter::once(ProfileChoice::BuiltIn)
+            .chain(
+                self.profiles
+                    .get_names()
+                    .into_iter()
+                    .map(ProfileChoice::Custom),
+            )
+            .collect();
 
         let players = column(
             self.players
@@ -133,22 +493,79 @@ impl GameSettings {
                             Some(match player {
                                 PlayerType::User => *player,
                                 PlayerType::Computer(_) => PlayerType::Computer(Bot::default()),
+                                PlayerType::External(_) => PlayerType::External(Bot::default()),
+                                PlayerType::Remote => *player,
+                            }),
+                            set_player_type,
+                        )
+                        .into(),
+                        radio("Remote", PlayerType::Remote, Some(*player), set_player_type).into(),
+                        radio(
+                            "External",
+                            PlayerType::External(Bot::default()),
+                            Some(match player {
+                                PlayerType::User => *player,
+                                PlayerType::Computer(_) => PlayerType::Computer(Bot::default()),
+                                PlayerType::External(_) => PlayerType::External(Bot::default()),
+                                PlayerType::Remote => *player,
                             }),
                             set_player_type,
                         )
                         .into(),
                         pick_list(
                             &Difficulty::ALL[..],
-                            player.get_bot().and_then(|bot| Some(bot.get_difficulty())),
-                            move |value| SettingsMessage::SetPlayerType(i, player.set_difficulty(value)).into(),
+                            player.get_bot().map(|bot| bot.get_difficulty()),
+                            move |value| {
+                                SettingsMessage::SetPlayerType(i, player.set_difficulty(value))
+                                    .into()
+                            },
                         )
                         .into(),
                         pick_list(
                             &Strategy::ALL[..],
-                            player.get_bot().and_then(|bot| Some(bot.get_strategy())),
-                            move |value| SettingsMessage::SetPlayerType(i, player.set_strategy(value)).into(),
+                            player.get_bot().map(|bot| bot.get_strategy()),
+                            move |value| {
+                                SettingsMessage::SetPlayerType(i, player.set_strategy(value)).into()
+                            },
                         )
                         .into(),
+                        pick_list(
+                            &Engine::ALL[..],
+                            player.get_bot().map(|bot| bot.get_engine()),
+                            move |value| {
+                                SettingsMessage::SetPlayerType(i, player.set_engine(value)).into()
+                            },
+                        )
+                        .into(),
+                        pick_list(
+                            profile_choices.clone(),
+                            player.get_bot().map(|_| {
+                                self.player_profiles[i]
+                                    .clone()
+                                    .map_or(ProfileChoice::BuiltIn, ProfileChoice::Custom)
+                            }),
+                            move |value| SettingsMessage::SetProfile(i, value).into(),
+                        )
+                        .into(),
+                        match player {
+                            PlayerType::User | PlayerType::Remote => {
+                                text_input(&format!("Player {}", i + 1), &self.names[i])
+                                    .on_input(move |value| {
+                                        SettingsMessage::SetName(i, value).into()
+                                    })
+                                    .width(Length::Fixed(150.0))
+                                    .into()
+                            }
+                            PlayerType::Computer(_) => Space::new(150, 0).into(),
+                            PlayerType::External(_) => {
+                                text_input("Engine command", &self.engine_commands[i])
+                                    .on_input(move |value| {
+                                        SettingsMessage::SetEngineCommand(i, value).into()
+                                    })
+                                    .width(Length::Fixed(150.0))
+                                    .into()
+                            }
+                        },
                         Space::new(10, 0).into(),
                         button("Delete")
                             .on_press(SettingsMessage::RemovePlayer(i).into())
@@ -174,17 +591,82 @@ impl GameSettings {
 
         let bottom_space = Space::new(0, Length::Fill).into();
 
-        let play_game = button(text("Start Game").size(30))
-            .on_press(Message::StartGame)
-            .into();
+        let load_game = column(vec![
+            row(vec![
+                text_input("Save file", &self.save_path)
+                    .on_input(|value| SettingsMessage::SetSavePath(value).into())
+                    .width(Length::Fixed(300.0))
+                    .into(),
+                button("Load Game").on_press(Message::LoadGame).into(),
+            ])
+            .align_items(iced::Alignment::Center)
+            .spacing(10)
+            .into(),
+            text(self.load_error.as_deref().unwrap_or_default()).into(),
+            error_text_message(self.weights_error.as_deref().unwrap_or_default()),
+            error_text_message(self.profiles_error.as_deref().unwrap_or_default()),
+            button("Reload Bot Files")
+                .on_press(SettingsMessage::ReloadBotFiles.into())
+                .style(theme::Button::Text)
+                .into(),
+        ])
+        .align_items(iced::Alignment::Center)
+        .into();
+
+        let network = column(vec![
+            row(vec![
+                text("Remote players join on port").into(),
+                text_input("", &self.port.to_string())
+                    .on_input(|value| SettingsMessage::ParsePort(value).into())
+                    .width(Length::Fixed(80.0))
+                    .into(),
+                Space::new(20, 0).into(),
+                text_input("Host address", &self.join_address)
+                    .on_input(|value| SettingsMessage::SetJoinAddress(value).into())
+                    .width(Length::Fixed(200.0))
+                    .into(),
+                button("Join Game").on_press(Message::JoinGame).into(),
+                button("Watch Game")
+                    .on_press(Message::WatchGame)
+                    .style(theme::Button::Secondary)
+                    .into(),
+            ])
+            .align_items(iced::Alignment::Center)
+            .spacing(10)
+            .into(),
+            error_text_message(self.network_error.as_deref().unwrap_or_default()),
+        ])
+        .align_items(iced::Alignment::Center)
+        .into();
+
+        let play_game = row(vec![
+            {
+                let button = button(text("Start Game").size(30));
+                if errors.is_empty() {
+                    button.on_press(Message::StartGame)
+                } else {
+                    button
+                }
+            }
+            .into(),
+            button(text("Leaderboard").size(30))
+                .on_press(Message::ShowLeaderboard)
+                .style(theme::Button::Secondary)
+                .into(),
+        ])
+        .spacing(20)
+        .into();
 
         let content = column(vec![
             title,
             numerical_input_values,
+            placement,
             player_title,
             players,
             add_player,
             bottom_space,
+            load_game,
+            network,
             play_game,
         ])
         .align_items(iced::Alignment::Center)
@@ -214,11 +696,61 @@ impl GameSettings {
                 }
                 SettingsMessage::RemovePlayer(index) => {
                     self.players.remove(index);
+                    self.names.remove(index);
+                    self.player_profiles.remove(index);
+                    self.engine_commands.remove(index);
                 }
-                SettingsMessage::AddPlayer => self.players.push(PlayerType::User),
+                SettingsMessage::AddPlayer => {
+                    self.players.push(PlayerType::User);
+                    self.names.push(String::new());
+                    self.player_profiles.push(None);
+                    self.engine_commands.push(String::new());
+                }
+                SettingsMessage::SetName(index, name) => {
+                    self.names[index] = name;
+                }
+                SettingsMessage::SetEngineCommand(index, command) => {
+                    self.engine_commands[index] = command;
+                }
+                SettingsMessage::SetProfile(index, choice) => {
+                    self.player_profiles[index] = match choice {
+                        ProfileChoice::BuiltIn => None,
+                        ProfileChoice::Custom(name) => Some(name),
+                    };
+                }
+                SettingsMessage::ReloadBotFiles => self.load_bot_files(),
                 SettingsMessage::SetPlayerType(index, player_type) => {
                     self.players[index] = player_type;
                 }
+                SettingsMessage::SetPlacement(placement) => {
+                    self.placement = placement;
+                }
+                SettingsMessage::SetDelay(delay) => {
+                    self.delay = delay;
+                }
+                SettingsMessage::ParseDelay(value) => {
+                    if let Ok(delay) = value.parse() {
+                        self.delay = delay;
+                    }
+                }
+                SettingsMessage::ParseSeed(value) => {
+                    if value.is_empty() {
+                        self.seed = None;
+                    } else if let Ok(seed) = value.parse() {
+                        self.seed = Some(seed);
+                    }
+                }
+                SettingsMessage::SetSavePath(path) => {
+                    self.save_path = path;
+                }
+                SettingsMessage::ParsePort(value) => {
+                    if let Ok(port) = value.parse() {
+                        self.port = port;
+                    }
+                }
+                SettingsMessage::SetJoinAddress(address) => {
+                    self.join_address = address;
+                }
                 SettingsMessage::ParseWidth(value) => {
                     if let Ok(width) = value.parse() {
                         self.width = width;
@@ -239,6 +771,27 @@ impl GameSettings {
     }
 }
 
+/// Lists the errors from `errors` that belong to a field, which are those in `field`
+fn error_text<'a>(
+    errors: &[NewGameError],
+    field: &[NewGameError],
+) -> Element<'a, Message, Renderer> {
+    let messages: Vec<_> = errors
+        .iter()
+        .filter(|error| field.contains(error))
+        .map(|error| error.to_string())
+        .collect();
+    error_text_message(&messages.join("\n"))
+}
+
+fn error_text_message<'a>(message: &str) -> Element<'a, Message, Renderer> {
+    text(message)
+        .size(16)
+        .style(Color::from_rgb(0.9, 0.2, 0.2))
+        .horizontal_alignment(iced::alignment::Horizontal::Center)
+        .into()
+}
+
 fn numerical_input<'a, Message: Clone + 'a, T: Into<Element<'a, Message, Renderer>>>(
     value: T,
     on_decrement: Option<Message>,
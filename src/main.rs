@@ -1,9 +1,8 @@
-use app::{GameMessage, GameSettings, SettingsMessage};
-use game::Game;
-use iced::{executor, Application, Command, Settings, Theme};
+use app::{GameMessage, GameScreen, GameSettings, Leaderboard, Network, SettingsMessage};
+use four_in_a_row_engine::{Game, PlayerType};
+use iced::{executor, subscription, window, Application, Command, Settings, Subscription, Theme};
 
 mod app;
-mod game;
 
 fn main() -> iced::Result {
     GameApp::run(Settings::default())
@@ -11,7 +10,8 @@ fn main() -> iced::Result {
 
 pub enum GameApp {
     GameSettings(GameSettings),
-    Playing(Game),
+    Playing(Box<GameScreen>),
+    Leaderboard(Leaderboard),
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +19,42 @@ pub enum Message {
     GameSettingsMessage(SettingsMessage),
     GameMessage(GameMessage),
     StartGame,
+    LoadGame,
+    JoinGame,
+    WatchGame,
+    ChangeSettings,
+    ShowLeaderboard,
+    Quit,
+}
+
+impl GameApp {
+    /// Starts playing `game`, hosting it if any of its players are remote
+    fn start_playing(&mut self, game: Game) -> Command<Message> {
+        if let Self::GameSettings(settings) = self {
+            let network = if game.get_players().contains(&PlayerType::Remote) {
+                match settings.start_host() {
+                    Some(host) => Some(Network::Host(host)),
+                    None => return Command::none(),
+                }
+            } else {
+                None
+            };
+            self.show_game(game, network)
+        } else {
+            Command::none()
+        }
+    }
+
+    fn show_game(&mut self, game: Game, network: Option<Network>) -> Command<Message> {
+        if let Self::GameSettings(settings) = self {
+            let mut screen = GameScreen::new(game, settings.clone(), network);
+            let command = screen.start();
+            *self = Self::Playing(Box::new(screen));
+            command
+        } else {
+            Command::none()
+        }
+    }
 }
 
 impl Application for GameApp {
@@ -31,7 +67,9 @@ impl Application for GameApp {
     type Flags = ();
 
     fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        (Self::GameSettings(GameSettings::default()), Command::none())
+        let mut settings = GameSettings::default();
+        settings.load_bot_files();
+        (Self::GameSettings(settings), Command::none())
     }
 
     fn title(&self) -> String {
@@ -42,18 +80,77 @@ impl Application for GameApp {
         match message {
             Message::StartGame => {
                 if let Self::GameSettings(settings) = self {
-                    *self = Self::Playing(settings.to_game());
+                    if let Ok(game) = settings.to_game() {
+                        return self.start_playing(game);
+                    }
+                }
+                Command::none()
+            }
+
+            Message::LoadGame => {
+                if let Self::GameSettings(settings) = self {
+                    if let Some(game) = settings.load_game() {
+                        return self.start_playing(game);
+                    }
+                }
+                Command::none()
+            }
+
+            Message::JoinGame => {
+                if let Self::GameSettings(settings) = self {
+                    if let Some((client, seat, game)) = settings.join_game() {
+                        return self.show_game(game, Some(Network::joined(client, seat)));
+                    }
                 }
                 Command::none()
             }
 
-            message => {
+            Message::WatchGame => {
+                if let Self::GameSettings(settings) = self {
+                    if let Some((client, game)) = settings.watch_game() {
+                        return self.show_game(game, Some(Network::spectating(client)));
+                    }
+                }
+                Command::none()
+            }
+
+            Message::ChangeSettings => {
                 match self {
-                    Self::GameSettings(settings) => settings.handle_message(message),
-                    Self::Playing(game) => game.handle_message(message),
+                    Self::Playing(screen) => {
+                        *self = Self::GameSettings(screen.get_settings().clone());
+                    }
+                    Self::Leaderboard(leaderboard) => {
+                        *self = Self::GameSettings(leaderboard.get_settings().clone());
+                    }
+                    Self::GameSettings(_) => {}
                 }
                 Command::none()
             }
+
+            Message::ShowLeaderboard => {
+                if let Self::GameSettings(settings) = self {
+                    *self = Self::Leaderboard(Leaderboard::new(settings.clone()));
+                }
+                Command::none()
+            }
+
+            Message::Quit => window::close(),
+
+            message => match self {
+                Self::GameSettings(settings) => {
+                    settings.handle_message(message);
+                    Command::none()
+                }
+                Self::Playing(game) => game.handle_message(message),
+                Self::Leaderboard(_) => Command::none(),
+            },
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        match self {
+            Self::GameSettings(_) | Self::Leaderboard(_) => Subscription::none(),
+            Self::Playing(_) => subscription::events_with(GameMessage::from_event),
         }
     }
 
@@ -61,6 +158,7 @@ impl Application for GameApp {
         match self {
             Self::GameSettings(settings) => settings.view(),
             Self::Playing(game) => game.view(),
+            Self::Leaderboard(leaderboard) => leaderboard.view(),
         }
     }
 }
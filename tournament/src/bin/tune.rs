@@ -0,0 +1,241 @@
+use std::{io::Write, path::PathBuf, sync::Arc, thread};
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use four_in_a_row_engine::{
+    Bot, ComputerWeights, Difficulty, Engine, Game, Placement, Profile, Strategy, WeightTable,
+};
+use four_in_a_row_tournament::{play_games, Board, Job, Standings};
+
+/// Improves the bot weights through self-play, writing out a new weight table
+///
+/// Each difficulty and strategy is tuned on its own with a simple evolution
+/// strategy: every generation nudges one or two of the incumbent weights, and
+/// the result replaces the incumbent if it beats it over a match.
+#[derive(Parser)]
+#[command(name = "tune")]
+struct Args {
+    /// Difficulties to tune. Repeat for several, or leave off to tune them all.
+    #[arg(long = "difficulty")]
+    difficulties: Vec<Difficulty>,
+
+    /// Strategies to tune. Repeat for several, or leave off to tune them all.
+    #[arg(long = "strategy")]
+    strategies: Vec<Strategy>,
+
+    /// Engine the bots search with while being tuned
+    #[arg(long, default_value_t = Engine::Heuristic)]
+    engine: Engine,
+
+    /// Board to play on, as WIDTHxHEIGHT:GOAL
+    #[arg(long, default_value = "7x6:4")]
+    board: Board,
+
+    #[arg(long, default_value_t = Placement::Gravity)]
+    placement: Placement,
+
+    #[arg(long, default_value_t = 30)]
+    generations: usize,
+
+    /// Games in each match between a candidate and the incumbent
+    #[arg(long, default_value_t = 40)]
+    games: usize,
+
+    /// Share of the points a candidate needs to replace the incumbent
+    #[arg(long, default_value_t = 0.55)]
+    threshold: f64,
+
+    /// Largest value any weight may take
+    #[arg(long, default_value_t = 10)]
+    max_weight: u64,
+
+    /// Weights to start from, defaulting to the built-in ones
+    #[arg(long)]
+    weights: Option<PathBuf>,
+
+    /// Where to write the tuned weights, which the app reads from weights.json
+    #[arg(long, default_value = "weights.json")]
+    output: PathBuf,
+
+    /// Games played at once, defaulting to one per CPU core
+    #[arg(long)]
+    threads: Option<usize>,
+
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+fn main() {
+    let args = Args::parse();
+    let board = args.board;
+    if let Some(error) = Game::validate(board.width, board.height, board.goal, 2).first() {
+        Args::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!("Can't play on {board}: {error}"),
+            )
+            .exit();
+    }
+    let threads = args.threads.unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(usize::from)
+            .unwrap_or(1)
+    });
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut table = match &args.weights {
+        Some(path) => WeightTable::load(path).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }),
+        None => WeightTable::default(),
+    };
+    let difficulties = or_all(&args.difficulties, &Difficulty::ALL);
+    let strategies = or_all(&args.strategies, &Strategy::ALL);
+
+    println!("Seed: {seed}");
+    for &difficulty in &difficulties {
+        for &strategy in &strategies {
+            let bot = Bot(difficulty, strategy, args.engine);
+            let original = table.get(difficulty, strategy);
+            let mut incumbent = original;
+
+            for generation in 1..=args.generations {
+                let candidate = mutate(incumbent, args.max_weight, &mut rng);
+                let score = play_match(&args, bot, candidate, incumbent, threads, &mut rng);
+                eprintln!(
+                    "{bot} generation {generation}: {} scored {:.0}%",
+                    format_weights(&candidate),
+                    score * 100.0
+                );
+                if score >= args.threshold {
+                    incumbent = candidate;
+                }
+            }
+
+            let score = play_match(&args, bot, incumbent, original, threads, &mut rng);
+            println!(
+                "{difficulty} {strategy}: {} -> {}, scoring {:.0}% against the original",
+                format_weights(&original),
+                format_weights(&incumbent),
+                score * 100.0
+            );
+            table.set(difficulty, strategy, incumbent);
+        }
+    }
+
+    if let Err(error) = table.save(&args.output) {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+    println!("Wrote {}", args.output.display());
+}
+
+fn or_all<T: Copy>(chosen: &[T], all: &[T]) -> Vec<T> {
+    if chosen.is_empty() {
+        all.to_vec()
+    } else {
+        chosen.to_vec()
+    }
+}
+
+/// Moves one or two of the weights up or down by one, keeping them in range
+fn mutate(weights: ComputerWeights, max_weight: u64, rng: &mut impl Rng) -> ComputerWeights {
+    let mut candidate = weights;
+    loop {
+        let mut fields = [
+            &mut candidate.computer,
+            &mut candidate.opponent,
+            &mut candidate.empty,
+            &mut candidate.populated,
+            &mut candidate.streak_computer,
+            &mut candidate.streak_opponent,
+        ];
+        fields.shuffle(rng);
+        for field in fields.into_iter().take(rng.gen_range(1..=2)) {
+            *field = if rng.gen() {
+                field.saturating_add(1).min(max_weight)
+            } else {
+                field.saturating_sub(1)
+            };
+        }
+        if candidate != weights {
+            return candidate;
+        }
+    }
+}
+
+/// Plays `candidate` against `incumbent`, with each moving first in half the
+/// games, and returns the share of the points the candidate scored
+fn play_match(
+    args: &Args,
+    bot: Bot,
+    candidate: ComputerWeights,
+    incumbent: ComputerWeights,
+    threads: usize,
+    rng: &mut impl Rng,
+) -> f64 {
+    let weight_table = Arc::new(WeightTable::default());
+    let profile = |name: &str, weights| Profile {
+        name: name.to_string(),
+        weights,
+        search_depth: None,
+        search_breadth: None,
+    };
+    let jobs = (0..args.games)
+        .map(|game| {
+            let seats = if game % 2 == 0 {
+                vec![0, 1]
+            } else {
+                vec![1, 0]
+            };
+            Job {
+                board: args.board,
+                placement: args.placement,
+                profiles: seats
+                    .iter()
+                    .map(|&entrant| {
+                        Some(if entrant == 0 {
+                            profile("Candidate", candidate)
+                        } else {
+                            profile("Incumbent", incumbent)
+                        })
+                    })
+                    .collect(),
+                engines: vec![None; seats.len()],
+                seats,
+                seed: rng.gen(),
+                weight_table: weight_table.clone(),
+            }
+        })
+        .collect();
+
+    let results = play_games(&[bot, bot], jobs, threads, |finished, total| {
+        eprint!("\r{finished}/{total} games");
+        let _ = std::io::stderr().flush();
+    });
+    eprint!("\r");
+    let results = results.unwrap_or_else(|error| {
+        eprintln!("Could not start a game: {error}");
+        std::process::exit(1);
+    });
+
+    let mut standings = Standings::new(2);
+    results
+        .iter()
+        .for_each(|result| standings.add_result(result));
+    standings.records[0].points / args.games.max(1) as f64
+}
+
+fn format_weights(weights: &ComputerWeights) -> String {
+    format!(
+        "[{} {} {} {} {} {}]",
+        weights.computer,
+        weights.opponent,
+        weights.empty,
+        weights.populated,
+        weights.streak_computer,
+        weights.streak_opponent
+    )
+}
@@ -0,0 +1,382 @@
+//! Plays many bot games in parallel, for the tournament runner and the weight
+//! tuner
+
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    str::FromStr,
+    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use four_in_a_row_engine::{
+    Bot, EngineCommand, ExternalEngine, Game, GameState, GameTrait, NewGameError, Placement,
+    PlayerType, Profile, WeightTable,
+};
+
+/// The size of the board and the length of line that wins on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    pub width: isize,
+    pub height: isize,
+    pub goal: isize,
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}:{}", self.width, self.height, self.goal)
+    }
+}
+
+/// Parses boards written as `WIDTHxHEIGHT:GOAL`, such as `7x6:4`
+impl FromStr for Board {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("\"{s}\" is not a board, expected WIDTHxHEIGHT:GOAL such as 7x6:4");
+        let (size, goal) = s.split_once(':').ok_or_else(error)?;
+        let (width, height) = size.split_once('x').ok_or_else(error)?;
+        Ok(Self {
+            width: width.trim().parse().map_err(|_| error())?,
+            height: height.trim().parse().map_err(|_| error())?,
+            goal: goal.trim().parse().map_err(|_| error())?,
+        })
+    }
+}
+
+/// A game to be played: where, by whom and in which seats
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub board: Board,
+    pub placement: Placement,
+    /// Indices into the list of bots, in the order they move
+    pub seats: Vec<usize>,
+    pub seed: u64,
+    pub weight_table: Arc<WeightTable>,
+    /// Profiles replacing the weights and search limits of the bot in each seat
+    pub profiles: Vec<Option<Profile>>,
+    /// The external engine playing each seat, with the seat's bot as its
+    /// fallback, or `None` for the bot itself
+    pub engines: Vec<Option<EngineCommand>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub job: Job,
+    /// How the game ended, with the winner given by seat
+    pub state: GameState,
+    /// Number of moves played
+    pub length: usize,
+    /// Moves each seat's engine failed to choose, which its fallback bot chose
+    /// instead
+    pub engine_failures: Vec<usize>,
+}
+
+impl GameResult {
+    /// The seat that won, or `None` for a draw
+    pub fn winner(&self) -> Option<usize> {
+        match &self.state {
+            GameState::PlayerWon(win) => Some(win.player),
+            _ => None,
+        }
+    }
+}
+
+/// Plays every job to the end on `threads` threads, calling `on_progress` with
+/// the number of games finished after each one. The results are in the order
+/// of `jobs`.
+pub fn play_games(
+    bots: &[Bot],
+    jobs: Vec<Job>,
+    threads: usize,
+    on_progress: impl Fn(usize, usize) + Sync,
+) -> Result<Vec<GameResult>, NewGameError> {
+    let next = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; jobs.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else {
+                    break;
+                };
+                let result = play_game(bots, job);
+                results.lock().unwrap()[index] = Some(result);
+                on_progress(finished.fetch_add(1, Ordering::Relaxed) + 1, jobs.len());
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every job is played"))
+        .collect()
+}
+
+fn play_game(bots: &[Bot], job: &Job) -> Result<GameResult, NewGameError> {
+    let mut game = Game::new(
+        job.board.width,
+        job.board.height,
+        job.board.goal,
+        job.seats
+            .iter()
+            .zip(&job.engines)
+            .map(|(&bot, engine)| match engine {
+                Some(_) => PlayerType::External(bots[bot]),
+                None => PlayerType::Computer(bots[bot]),
+            })
+            .collect(),
+        job.placement,
+    )?;
+    game.set_seed(job.seed);
+    game.set_weight_table(job.weight_table.clone());
+    for (seat, profile) in job.profiles.iter().enumerate() {
+        game.set_player_profile(seat, profile.clone());
+    }
+    let mut engines: Vec<_> = job
+        .engines
+        .iter()
+        .map(|command| command.clone().map(ExternalEngine::new))
+        .collect();
+    let mut engine_failures = vec![0; job.seats.len()];
+
+    while let Some(bot) = game.get_waiting_bot() {
+        let seat = game.get_current_player();
+        let point = match &mut engines[seat] {
+            Some(engine) => {
+                let (point, error) = game.get_external_move(engine, bot);
+                engine_failures[seat] += usize::from(error.is_some());
+                point
+            }
+            None => game.get_computer_move(bot),
+        };
+        match point {
+            Some(point) => game
+                .play_computer_move(point)
+                .expect("bots only choose valid moves"),
+            None => break,
+        }
+    }
+
+    Ok(GameResult {
+        state: game.get_gamestate(),
+        length: game.get_history().len(),
+        engine_failures,
+        job: job.clone(),
+    })
+}
+
+/// Every way of choosing `size` of `count` bots, each in increasing order
+pub fn round_robin_tables(count: usize, size: usize) -> Vec<Vec<usize>> {
+    fn extend(table: &mut Vec<usize>, start: usize, count: usize, size: usize) -> Vec<Vec<usize>> {
+        if table.len() == size {
+            return vec![table.clone()];
+        }
+        let mut tables = Vec::new();
+        for bot in start..count {
+            table.push(bot);
+            tables.extend(extend(table, bot + 1, count, size));
+            table.pop();
+        }
+        tables
+    }
+    extend(&mut Vec::new(), 0, count, size)
+}
+
+/// Seats bots with similar scores together for a round of a Swiss tournament.
+/// `standings` lists the bots from best to worst, and bots that have already met
+/// are kept apart where the order allows. Bots left over when the field does
+/// not divide into tables sit the round out and are returned separately; they
+/// are the lowest ranked of those with the fewest byes so far.
+pub fn swiss_tables(
+    standings: &[usize],
+    size: usize,
+    met: &HashSet<(usize, usize)>,
+    bye_counts: &[usize],
+) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut by_bye_priority: Vec<_> = standings.iter().rev().copied().collect();
+    by_bye_priority.sort_by_key(|&bot| bye_counts[bot]);
+    let byes = by_bye_priority[..standings.len() % size].to_vec();
+    let mut waiting: Vec<_> = standings
+        .iter()
+        .copied()
+        .filter(|bot| !byes.contains(bot))
+        .collect();
+
+    let mut tables = Vec::new();
+    while !waiting.is_empty() {
+        let mut table = vec![waiting.remove(0)];
+        while table.len() < size {
+            let fresh = waiting.iter().position(|candidate| {
+                table
+                    .iter()
+                    .all(|seated| !met.contains(&pair(*seated, *candidate)))
+            });
+            table.push(waiting.remove(fresh.unwrap_or(0)));
+        }
+        tables.push(table);
+    }
+    (tables, byes)
+}
+
+/// The two bots ordered so either way round gives the same key
+pub fn pair(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// Every game at a table, with the seats rotated by one each game so each bot
+/// moves first as often as the others. `engines` gives the external engine of
+/// each bot, if it has one.
+pub fn table_jobs(
+    table: &[usize],
+    engines: &[Option<EngineCommand>],
+    board: Board,
+    placement: Placement,
+    weight_table: &Arc<WeightTable>,
+    games: usize,
+    seed: &mut u64,
+) -> Vec<Job> {
+    (0..games)
+        .map(|game| {
+            let mut seats = table.to_vec();
+            seats.rotate_left(game % table.len());
+            *seed = seed.wrapping_add(1);
+            Job {
+                board,
+                placement,
+                profiles: vec![None; seats.len()],
+                engines: seats.iter().map(|&bot| engines[bot].clone()).collect(),
+                seats,
+                seed: *seed,
+                weight_table: weight_table.clone(),
+            }
+        })
+        .collect()
+}
+
+/// How one bot has done across every game it played
+#[derive(Debug, Clone, Default)]
+pub struct Record {
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// A point per win, shared between the players of a draw, and one per bye
+    pub points: f64,
+    pub byes: usize,
+    /// Total moves in every game played, for the average length
+    pub moves: usize,
+    pub first_games: usize,
+    pub first_wins: usize,
+    /// Moves the bot's engine failed to choose, leaving them to its fallback
+    pub engine_failures: usize,
+}
+
+impl Record {
+    pub fn average_length(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.moves as f64 / self.games as f64
+        }
+    }
+}
+
+/// The results of a whole tournament, by bot, by seat and head to head
+#[derive(Debug, Clone)]
+pub struct Standings {
+    pub records: Vec<Record>,
+    /// Wins by seat for each player count, indexed by player count then seat
+    pub seat_wins: Vec<Vec<usize>>,
+    /// Games and draws for each player count
+    pub games_by_size: Vec<(usize, usize)>,
+    /// Points each bot took from each other bot in two player games
+    pub head_to_head: Vec<Vec<f64>>,
+    pub met: HashSet<(usize, usize)>,
+}
+
+impl Standings {
+    pub fn new(bot_count: usize) -> Self {
+        Self {
+            records: vec![Record::default(); bot_count],
+            seat_wins: Vec::new(),
+            games_by_size: Vec::new(),
+            head_to_head: vec![vec![0.0; bot_count]; bot_count],
+            met: HashSet::new(),
+        }
+    }
+
+    pub fn add_result(&mut self, result: &GameResult) {
+        let seats = &result.job.seats;
+        let size = seats.len();
+        if self.seat_wins.len() <= size {
+            self.seat_wins.resize(size + 1, Vec::new());
+            self.games_by_size.resize(size + 1, (0, 0));
+        }
+        self.seat_wins[size].resize(size, 0);
+        self.games_by_size[size].0 += 1;
+        match result.winner() {
+            Some(seat) => self.seat_wins[size][seat] += 1,
+            None => self.games_by_size[size].1 += 1,
+        }
+
+        for (seat, &bot) in seats.iter().enumerate() {
+            let record = &mut self.records[bot];
+            record.games += 1;
+            record.moves += result.length;
+            record.engine_failures += result.engine_failures[seat];
+            match result.winner() {
+                Some(winner) if winner == seat => {
+                    record.wins += 1;
+                    record.points += 1.0;
+                }
+                Some(_) => record.losses += 1,
+                None => {
+                    record.draws += 1;
+                    record.points += 1.0 / size as f64;
+                }
+            }
+            if seat == 0 {
+                record.first_games += 1;
+                record.first_wins += usize::from(result.winner() == Some(0));
+            }
+            for &other in seats.iter().filter(|&&other| other != bot) {
+                self.met.insert(pair(bot, other));
+            }
+        }
+
+        if let [a, b] = seats[..] {
+            match result.winner() {
+                Some(0) => self.head_to_head[a][b] += 1.0,
+                Some(_) => self.head_to_head[b][a] += 1.0,
+                None => {
+                    self.head_to_head[a][b] += 0.5;
+                    self.head_to_head[b][a] += 0.5;
+                }
+            }
+        }
+    }
+
+    pub fn add_bye(&mut self, bot: usize) {
+        self.records[bot].byes += 1;
+        self.records[bot].points += 1.0;
+    }
+
+    /// Every bot from most to fewest points, with ties broken by wins
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<_> = (0..self.records.len()).collect();
+        ranking.sort_by(|&a, &b| {
+            let (a, b) = (&self.records[a], &self.records[b]);
+            b.points.total_cmp(&a.points).then(b.wins.cmp(&a.wins))
+        });
+        ranking
+    }
+}
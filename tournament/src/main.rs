@@ -0,0 +1,389 @@
+use std::{io::Write, path::PathBuf, sync::Arc, thread};
+
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+
+use four_in_a_row_engine::{
+    Bot, Competitor, EngineCommand, Game, Placement, Ratings, WeightTable, DEFAULT_ENGINE_TIMEOUT,
+};
+
+use four_in_a_row_tournament::{
+    play_games, round_robin_tables, swiss_tables, table_jobs, Board, Job, Standings,
+};
+
+/// Plays computer players against each other and reports how they did
+#[derive(Parser)]
+#[command(name = "tournament")]
+struct Args {
+    /// Bots to enter, written as difficulty:strategy:engine, such as
+    /// hard:offensive:alpha-beta. The strategy and engine can be left off.
+    bots: Vec<Bot>,
+
+    /// External engine to enter, as the command line that starts it, such as
+    /// "python3 scripts/mock_engine.py". Repeat to enter several.
+    #[arg(long = "engine")]
+    engines: Vec<EngineCommand>,
+
+    /// Bot that moves for an external engine whenever it fails to
+    #[arg(long, default_value = "normal")]
+    fallback: Bot,
+
+    /// Longest in milliseconds to wait for each answer from an external engine
+    #[arg(long, default_value_t = DEFAULT_ENGINE_TIMEOUT)]
+    engine_timeout: u64,
+
+    /// File to log everything said to and by the external engines to
+    #[arg(long)]
+    engine_log: Option<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = Format::RoundRobin)]
+    format: Format,
+
+    /// Rounds to play in a Swiss tournament
+    #[arg(long, default_value_t = 5)]
+    rounds: usize,
+
+    /// Board to play on, as WIDTHxHEIGHT:GOAL. Repeat to play on several boards.
+    #[arg(long = "board", default_value = "7x6:4")]
+    boards: Vec<Board>,
+
+    /// Players in each game. Repeat to play with several player counts.
+    #[arg(long = "players", default_value = "2")]
+    player_counts: Vec<usize>,
+
+    #[arg(long, default_value_t = Placement::Gravity)]
+    placement: Placement,
+
+    /// Games each table plays on each board, with the seats rotated every game
+    #[arg(long, default_value_t = 2)]
+    games: usize,
+
+    /// Games played at once, defaulting to one per CPU core
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Seed for the first game, so a tournament can be replayed exactly
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Weights file for the bots to play with instead of the built-in weights
+    #[arg(long)]
+    weights: Option<PathBuf>,
+
+    /// Ratings file to add every game to, such as the app's ratings.json
+    #[arg(long)]
+    ratings: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Every combination of bots meets at every board and player count
+    RoundRobin,
+    /// Bots with similar scores meet each round
+    Swiss,
+}
+
+fn main() {
+    let args = Args::parse();
+    validate(&args);
+
+    let threads = args.threads.unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(usize::from)
+            .unwrap_or(1)
+    });
+    let first_seed = args.seed.unwrap_or_else(rand::random);
+    let mut seed = first_seed;
+    let (bots, engines) = entrants(&args);
+    let mut standings = Standings::new(bots.len());
+    let weight_table = Arc::new(match &args.weights {
+        Some(path) => WeightTable::load(path).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }),
+        None => WeightTable::default(),
+    });
+    let mut ratings = args.ratings.as_ref().map(|path| {
+        Ratings::load(path).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1);
+        })
+    });
+
+    println!("Seed: {first_seed}");
+    match args.format {
+        Format::RoundRobin => {
+            let mut jobs = Vec::new();
+            for (board, size) in configurations(&args) {
+                for table in round_robin_tables(bots.len(), size) {
+                    jobs.extend(table_jobs(
+                        &table,
+                        &engines,
+                        board,
+                        args.placement,
+                        &weight_table,
+                        args.games,
+                        &mut seed,
+                    ));
+                }
+            }
+            play_round(
+                &bots,
+                &engines,
+                jobs,
+                threads,
+                "Round robin",
+                &mut standings,
+                &mut ratings,
+            );
+        }
+        Format::Swiss => {
+            for round in 1..=args.rounds {
+                let ranking = standings.ranking();
+                let mut jobs = Vec::new();
+                for (board, size) in configurations(&args) {
+                    let bye_counts: Vec<_> =
+                        standings.records.iter().map(|record| record.byes).collect();
+                    let (tables, byes) = swiss_tables(&ranking, size, &standings.met, &bye_counts);
+                    for table in tables {
+                        jobs.extend(table_jobs(
+                            &table,
+                            &engines,
+                            board,
+                            args.placement,
+                            &weight_table,
+                            args.games,
+                            &mut seed,
+                        ));
+                    }
+                    byes.into_iter().for_each(|bot| standings.add_bye(bot));
+                }
+                play_round(
+                    &bots,
+                    &engines,
+                    jobs,
+                    threads,
+                    &format!("Round {round}"),
+                    &mut standings,
+                    &mut ratings,
+                );
+            }
+        }
+    }
+
+    let names: Vec<_> = bots
+        .iter()
+        .zip(&engines)
+        .map(|(bot, engine)| match engine {
+            Some(command) => format!("{command} (external)"),
+            None => bot.to_string(),
+        })
+        .collect();
+    print_standings(&names, &standings);
+
+    if let (Some(path), Some(ratings)) = (&args.ratings, ratings) {
+        if let Err(error) = ratings.save(path) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The bots entered and then the external engines, as the bot of each entrant
+/// along with its engine if it has one. An engine's bot is its fallback.
+fn entrants(args: &Args) -> (Vec<Bot>, Vec<Option<EngineCommand>>) {
+    let engines = args.engines.iter().map(|command| EngineCommand {
+        timeout: args.engine_timeout,
+        log: args.engine_log.clone(),
+        ..command.clone()
+    });
+    args.bots
+        .iter()
+        .map(|&bot| (bot, None))
+        .chain(engines.map(|command| (args.fallback, Some(command))))
+        .unzip()
+}
+
+/// Plays a batch of games, showing progress on stderr, and records the results
+fn play_round(
+    bots: &[Bot],
+    engines: &[Option<EngineCommand>],
+    jobs: Vec<Job>,
+    threads: usize,
+    name: &str,
+    standings: &mut Standings,
+    ratings: &mut Option<Ratings>,
+) {
+    let results = play_games(bots, jobs, threads, |finished, total| {
+        eprint!("\r{name}: {finished}/{total} games");
+        let _ = std::io::stderr().flush();
+    });
+    eprintln!();
+    match results {
+        Ok(results) => {
+            for result in results {
+                standings.add_result(&result);
+                if let Some(ratings) = ratings {
+                    let competitors: Vec<_> = result
+                        .job
+                        .seats
+                        .iter()
+                        .map(|&bot| match &engines[bot] {
+                            Some(command) => Competitor::External(command.to_string()),
+                            None => Competitor::Computer(bots[bot]),
+                        })
+                        .collect();
+                    ratings.record_game(&competitors, &result.state);
+                }
+            }
+        }
+        Err(error) => {
+            eprintln!("Could not start a game: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_standings(names: &[String], standings: &Standings) {
+    let name_width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default()
+        .max(3);
+
+    println!();
+    println!(
+        "{:>3}  {:<name_width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>6}  {:>4}  {:>10}  {:>11}",
+        "#",
+        "Bot",
+        "Games",
+        "Wins",
+        "Draws",
+        "Losses",
+        "Points",
+        "Byes",
+        "Avg length",
+        "First moves"
+    );
+    for (rank, &bot) in standings.ranking().iter().enumerate() {
+        let record = &standings.records[bot];
+        println!(
+            "{:>3}  {:<name_width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>6.1}  {:>4}  {:>10.1}  {:>11}",
+            rank + 1,
+            names[bot],
+            record.games,
+            record.wins,
+            record.draws,
+            record.losses,
+            record.points,
+            record.byes,
+            record.average_length(),
+            format!("{}/{} won", record.first_wins, record.first_games),
+        );
+    }
+
+    for (size, seat_wins) in standings.seat_wins.iter().enumerate() {
+        let (games, draws) = standings.games_by_size[size];
+        if games == 0 {
+            continue;
+        }
+        println!();
+        println!("Wins by seat in {size} player games ({games} games, {draws} drawn)");
+        for (seat, wins) in seat_wins.iter().enumerate() {
+            println!(
+                "  Seat {}: {wins:>5} ({:.1}%)",
+                seat + 1,
+                100.0 * *wins as f64 / games as f64
+            );
+        }
+    }
+
+    if standings
+        .games_by_size
+        .get(2)
+        .is_some_and(|(games, _)| *games > 0)
+    {
+        println!();
+        println!("Points scored by each row against each column in 2 player games");
+        print!("{:>3}  {:<name_width$}", "", "");
+        (1..=names.len()).for_each(|column| print!("  {column:>5}"));
+        println!();
+        for (row, name) in names.iter().enumerate() {
+            print!("{:>3}  {:<name_width$}", row + 1, name);
+            for column in 0..names.len() {
+                if row == column {
+                    print!("  {:>5}", "-");
+                } else {
+                    print!("  {:>5.1}", standings.head_to_head[row][column]);
+                }
+            }
+            println!();
+        }
+    }
+
+    if standings
+        .records
+        .iter()
+        .any(|record| record.engine_failures > 0)
+    {
+        println!();
+        println!("Moves the fallback bot made after an external engine failed");
+        for (name, record) in names.iter().zip(&standings.records) {
+            if record.engine_failures > 0 {
+                println!("  {name}: {}", record.engine_failures);
+            }
+        }
+    }
+}
+
+/// Every board paired with every player count
+fn configurations(args: &Args) -> impl Iterator<Item = (Board, usize)> + '_ {
+    args.boards
+        .iter()
+        .flat_map(|&board| args.player_counts.iter().map(move |&size| (board, size)))
+}
+
+fn validate(args: &Args) {
+    let mut command = Args::command();
+    let entrants = args.bots.len() + args.engines.len();
+    if entrants < 2 {
+        command
+            .error(
+                ErrorKind::TooFewValues,
+                "At least 2 bots or engines must be entered",
+            )
+            .exit();
+    }
+    for (board, size) in configurations(args) {
+        if let Some(error) = Game::validate(board.width, board.height, board.goal, size).first() {
+            command
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("Can't play {size} players on {board}: {error}"),
+                )
+                .exit();
+        }
+        if size > entrants {
+            command
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("Games of {size} players need at least {size} bots or engines"),
+                )
+                .exit();
+        }
+        let fallback = (!args.engines.is_empty()).then_some(&args.fallback);
+        if let Some(bot) = args
+            .bots
+            .iter()
+            .chain(fallback)
+            .find(|bot| !bot.get_engine().supports(size))
+        {
+            command
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("{bot} can't play games of {size} players"),
+                )
+                .exit();
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use four_in_a_row_engine::{
+    Bot, Game, GameState, GameTrait, Hint, Placement, Player, PlayerType, Point, PLAYER_COLORS,
+};
+
+use crate::settings::Settings;
+
+/// Columns of the terminal each cell takes up
+const CELL_WIDTH: u16 = 3;
+
+pub struct GameView {
+    game: Game,
+    /// The settings the game was started from, returned to when changing settings
+    settings: Settings,
+    /// The cell a person's move goes to. With gravity only its column matters.
+    cursor: Point,
+    /// The computer move or hint being worked out on another thread. Dropping it
+    /// throws the result away.
+    work: Option<Work>,
+    /// The move recommended to the player whose turn it is, until they move
+    hint: Option<Hint>,
+}
+
+enum Work {
+    Move(Receiver<Option<Point>>),
+    Hint(Receiver<Option<Hint>>),
+}
+
+/// What the game wants done after a key press
+pub enum GameAction {
+    None,
+    ChangeSettings,
+    Quit,
+}
+
+impl GameView {
+    pub fn new(game: Game, settings: Settings) -> Self {
+        Self {
+            cursor: Point::new(game.get_width() / 2, 0),
+            game,
+            settings,
+            work: None,
+            hint: None,
+        }
+    }
+
+    pub fn into_settings(self) -> Settings {
+        self.settings
+    }
+
+    /// Picks up finished background work, and starts the next computer move if
+    /// it is a computer player's turn
+    pub fn tick(&mut self) {
+        match &self.work {
+            Some(Work::Move(receiver)) => match receiver.try_recv() {
+                Ok(point) => {
+                    self.work = None;
+                    if let Some(point) = point {
+                        self.game.play_computer_move(point).ok();
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.work = None,
+            },
+            Some(Work::Hint(receiver)) => match receiver.try_recv() {
+                Ok(hint) => {
+                    self.work = None;
+                    self.hint = hint;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.work = None,
+            },
+            None => {}
+        }
+
+        if let (None, Some(bot)) = (&self.work, self.game.get_waiting_bot()) {
+            self.work = Some(Work::Move(think(
+                self.game.clone(),
+                bot,
+                self.settings.delay,
+            )));
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> GameAction {
+        let busy = self.work.is_some();
+        match key.code {
+            KeyCode::Char('q') => return GameAction::Quit,
+            KeyCode::Esc => return GameAction::ChangeSettings,
+            KeyCode::Left => self.move_cursor(-1, 0),
+            KeyCode::Right => self.move_cursor(1, 0),
+            KeyCode::Up => self.move_cursor(0, -1),
+            KeyCode::Down => self.move_cursor(0, 1),
+            KeyCode::Enter | KeyCode::Char(' ') if !busy => self.play(self.cursor),
+            KeyCode::Char(digit @ '1'..='9')
+                if !busy && self.game.get_placement() == Placement::Gravity =>
+            {
+                let column = digit as isize - '1' as isize;
+                if column < self.game.get_width() {
+                    self.cursor.x = column;
+                    self.play(self.cursor);
+                }
+            }
+            KeyCode::Char('u') => {
+                self.work = None;
+                self.hint = None;
+                self.game.undo();
+            }
+            KeyCode::Char('r') => {
+                self.work = None;
+                self.hint = None;
+                self.game.redo();
+            }
+            KeyCode::Char('?') if self.can_hint() => {
+                self.game.record_hint(self.game.get_current_player());
+                self.work = Some(Work::Hint(find_hint(self.game.clone())));
+            }
+            KeyCode::Char(key @ ('n' | 'N'))
+                if !matches!(self.game.get_gamestate(), GameState::PlayerMove(_)) =>
+            {
+                self.work = None;
+                self.hint = None;
+                self.game = self.game.rematch(key == 'N');
+            }
+            _ => {}
+        }
+        GameAction::None
+    }
+
+    fn move_cursor(&mut self, x: isize, y: isize) {
+        self.cursor.x = (self.cursor.x + x).clamp(0, self.game.get_width() - 1);
+        self.cursor.y = (self.cursor.y + y).clamp(0, self.game.get_height() - 1);
+    }
+
+    fn play(&mut self, point: Point) {
+        if self.game.get_waiting_bot().is_none() && self.game.play_move(point).is_ok() {
+            self.hint = None;
+        }
+    }
+
+    /// Whether the player to move is a person who can ask for a hint right now
+    fn can_hint(&self) -> bool {
+        matches!(self.game.get_gamestate(), GameState::PlayerMove(_))
+            && self.game.get_waiting_bot().is_none()
+            && self.work.is_none()
+            && self.hint.is_none()
+    }
+
+    /// The cell the cursor would play into, if it can be played
+    fn get_cursor_cell(&self) -> Option<Point> {
+        match self.game.get_gamestate() {
+            GameState::PlayerMove(_) if self.game.get_waiting_bot().is_none() => {
+                self.game.get_landing_point(&self.cursor).ok()
+            }
+            _ => None,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let board = self.board_lines();
+        let board_width = self.game.get_width() as u16 * CELL_WIDTH + 2;
+        let board_height = board.len() as u16 + 2;
+
+        let [status, board_area, info, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .areas(area);
+        let [board_area] = Layout::horizontal([Constraint::Length(board_width)])
+            .flex(Flex::Center)
+            .areas(board_area);
+
+        frame.render_widget(Paragraph::new(self.status_line()).centered(), status);
+        frame.render_widget(Paragraph::new(board).block(Block::bordered()), board_area);
+        frame.render_widget(Paragraph::new(self.info_lines()).centered(), info);
+        frame.render_widget(Paragraph::new(self.help_line()).centered().dim(), help);
+    }
+
+    fn status_line(&self) -> Line<'_> {
+        match self.game.get_gamestate() {
+            GameState::PlayerMove(player) => {
+                let name = player_name(player);
+                match self.game.get_players()[player] {
+                    PlayerType::Computer(bot) | PlayerType::External(bot) => {
+                        Line::from(format!("{name} ({bot}) is thinking…"))
+                    }
+                    PlayerType::User | PlayerType::Remote => Line::from(format!("{name} to move")),
+                }
+                .fg(player_color(player))
+            }
+            GameState::PlayerWon(win) => Line::from(format!("{} wins!", player_name(win.player)))
+                .fg(player_color(win.player))
+                .bold(),
+            GameState::Draw => Line::from("It's a draw!").bold(),
+        }
+    }
+
+    fn board_lines(&self) -> Vec<Line<'_>> {
+        let width = self.game.get_width();
+        let win = match self.game.get_gamestate() {
+            GameState::PlayerWon(win) => Some(win),
+            _ => None,
+        };
+        let cursor = self.get_cursor_cell();
+        let hint = self.hint.map(|hint| hint.point);
+
+        let mut lines = vec![Line::from(
+            (0..width)
+                .map(|x| {
+                    let label = format!("{:^3}", x + 1);
+                    if x == self.cursor.x && cursor.is_some() {
+                        Span::raw(label).bold()
+                    } else {
+                        Span::raw(label).dim()
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )];
+
+        lines.extend(
+            self.game
+                .get_board()
+                .into_iter()
+                .enumerate()
+                .map(|(y, row)| {
+                    Line::from(
+                        row.into_iter()
+                            .enumerate()
+                            .map(|(x, tile)| {
+                                let point = Point::new(x as isize, y as isize);
+                                let mut style = Style::new();
+                                if Some(point) == cursor {
+                                    style = style.bg(Color::DarkGray);
+                                }
+                                let symbol = match tile {
+                                    Some(player) => {
+                                        style = style.fg(player_color(player));
+                                        match &win {
+                                            Some(win) if win.contains(&point) => {
+                                                style = style.add_modifier(Modifier::REVERSED);
+                                            }
+                                            Some(_) => style = style.add_modifier(Modifier::DIM),
+                                            None => {}
+                                        }
+                                        " ● "
+                                    }
+                                    None if Some(point) == hint => {
+                                        style = style
+                                            .fg(player_color(self.game.get_current_player()))
+                                            .bold();
+                                        " ◆ "
+                                    }
+                                    None => {
+                                        style = style.add_modifier(Modifier::DIM);
+                                        " · "
+                                    }
+                                };
+                                Span::styled(symbol, style)
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                }),
+        );
+        lines
+    }
+
+    /// The hint and its reason, then the seed and hints used
+    fn info_lines(&self) -> Vec<Line<'_>> {
+        let hint = match (&self.work, &self.hint) {
+            (Some(Work::Hint(_)), _) => String::from("Finding a hint…"),
+            (_, Some(hint)) => {
+                let place = match self.game.get_placement() {
+                    Placement::Gravity => format!("Hint: column {}", hint.point.x + 1),
+                    Placement::Free => format!(
+                        "Hint: column {}, row {}",
+                        hint.point.x + 1,
+                        hint.point.y + 1
+                    ),
+                };
+                match hint.reason {
+                    Some(reason) => format!("{place}: {reason}"),
+                    None => place,
+                }
+            }
+            _ => String::new(),
+        };
+
+        let mut details = vec![format!("Seed: {}", self.game.get_seed())];
+        let counts = self.game.get_hint_counts();
+        if counts.iter().any(|&count| count > 0) {
+            let counts: Vec<_> = counts
+                .iter()
+                .enumerate()
+                .map(|(player, count)| format!("{}: {count}", player_name(player)))
+                .collect();
+            details.push(format!("Hints used: {}", counts.join(", ")));
+        }
+
+        vec![Line::from(hint), Line::from(details.join("  "))]
+    }
+
+    fn help_line(&self) -> Line<'_> {
+        if !matches!(self.game.get_gamestate(), GameState::PlayerMove(_)) {
+            return Line::from(
+                "n rematch  N rematch, next player first  u undo  r redo  Esc settings  q quit",
+            );
+        }
+        match self.game.get_placement() {
+            Placement::Gravity => Line::from(
+                "←→ or 1-9 column  Enter play  ? hint  u undo  r redo  Esc settings  q quit",
+            ),
+            Placement::Free => {
+                Line::from("←↑↓→ cell  Enter play  ? hint  u undo  r redo  Esc settings  q quit")
+            }
+        }
+    }
+}
+
+/// Works out a computer move on its own thread, taking at least `delay` so
+/// games between bots can be followed
+fn think(game: Game, bot: Bot, delay: Duration) -> Receiver<Option<Point>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let point = game.get_computer_move(bot);
+        if let Some(remaining) = delay.checked_sub(start.elapsed()) {
+            thread::sleep(remaining);
+        }
+        sender.send(point).ok();
+    });
+    receiver
+}
+
+/// Works out a hint on its own thread
+fn find_hint(game: Game) -> Receiver<Option<Hint>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        sender.send(game.get_hint()).ok();
+    });
+    receiver
+}
+
+fn player_name(player: Player) -> String {
+    format!("Player {}", player + 1)
+}
+
+pub fn player_color(player: Player) -> Color {
+    let [r, g, b] = PLAYER_COLORS.get(player).copied().unwrap_or([255; 3]);
+    Color::Rgb(r, g, b)
+}
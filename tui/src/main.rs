@@ -0,0 +1,157 @@
+mod game;
+mod settings;
+
+use std::{io, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    DefaultTerminal,
+};
+
+use four_in_a_row_engine::{Placement, PlayerType, WeightTable};
+
+use game::{GameAction, GameView};
+use settings::{Settings, SettingsAction, MAX_PLAYERS};
+
+/// How often the screen is redrawn while waiting for a key, to pick up
+/// computer moves
+const TICK: Duration = Duration::from_millis(50);
+
+/// Plays in the terminal, with the same options as the app
+#[derive(Parser)]
+#[command(name = "tui")]
+struct Args {
+    #[arg(long, default_value_t = 6)]
+    width: isize,
+
+    #[arg(long, default_value_t = 6)]
+    height: isize,
+
+    /// Pieces in a row needed to win
+    #[arg(long, default_value_t = 4)]
+    goal: isize,
+
+    #[arg(long, default_value_t = Placement::Gravity)]
+    placement: Placement,
+
+    /// Who plays each seat, in turn order: user, or a bot written as
+    /// difficulty:strategy:engine such as hard:offensive:alpha-beta. Repeat
+    /// for each player.
+    #[arg(long = "player", default_values = ["user", "normal"])]
+    players: Vec<PlayerType>,
+
+    /// Seed for the computer players, so a game can be replayed exactly
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Minimum time in milliseconds each computer move takes
+    #[arg(long, default_value_t = 0)]
+    delay: u64,
+
+    /// Weights for the bots to play with, if the file exists
+    #[arg(long, default_value = "weights.json")]
+    weights: PathBuf,
+
+    /// Start playing straight away instead of showing the settings first
+    #[arg(long)]
+    play: bool,
+}
+
+enum Screen {
+    Settings(Settings),
+    Playing(Box<GameView>),
+}
+
+fn main() {
+    let args = Args::parse();
+    if !(2..=MAX_PLAYERS).contains(&args.players.len()) {
+        Args::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!("There must be from 2 to {MAX_PLAYERS} players"),
+            )
+            .exit();
+    }
+
+    let mut settings = Settings::new(
+        args.width,
+        args.height,
+        args.goal,
+        args.placement,
+        args.players,
+    );
+    settings.seed = args.seed;
+    settings.delay = Duration::from_millis(args.delay);
+    settings.weight_table = Arc::new(WeightTable::load(&args.weights).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }));
+
+    let screen = if args.play {
+        match settings.to_game() {
+            Ok(game) => Screen::Playing(Box::new(GameView::new(game, settings))),
+            Err(error) => Args::command()
+                .error(ErrorKind::ValueValidation, error.to_string())
+                .exit(),
+        }
+    } else {
+        Screen::Settings(settings)
+    };
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, screen);
+    ratatui::restore();
+    if let Err(error) = result {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, mut screen: Screen) -> io::Result<()> {
+    loop {
+        if let Screen::Playing(view) = &mut screen {
+            view.tick();
+        }
+        terminal.draw(|frame| match &screen {
+            Screen::Settings(settings) => settings.draw(frame, frame.area()),
+            Screen::Playing(view) => view.draw(frame, frame.area()),
+        })?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(());
+        }
+        screen = match handle_key(screen, key) {
+            ControlFlow::Continue(screen) => screen,
+            ControlFlow::Break(()) => return Ok(()),
+        };
+    }
+}
+
+fn handle_key(screen: Screen, key: event::KeyEvent) -> ControlFlow<(), Screen> {
+    match screen {
+        Screen::Settings(mut settings) => match settings.handle_key(key) {
+            SettingsAction::None => ControlFlow::Continue(Screen::Settings(settings)),
+            SettingsAction::Start(game) => {
+                ControlFlow::Continue(Screen::Playing(Box::new(GameView::new(*game, settings))))
+            }
+            SettingsAction::Quit => ControlFlow::Break(()),
+        },
+        Screen::Playing(mut view) => match view.handle_key(key) {
+            GameAction::None => ControlFlow::Continue(Screen::Playing(view)),
+            GameAction::ChangeSettings => {
+                ControlFlow::Continue(Screen::Settings(view.into_settings()))
+            }
+            GameAction::Quit => ControlFlow::Break(()),
+        },
+    }
+}
@@ -0,0 +1,238 @@
+use std::{sync::Arc, time::Duration};
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use four_in_a_row_engine::{
+    Bot, Difficulty, Engine, Game, NewGameError, Placement, PlayerType, Strategy, WeightTable,
+};
+
+use crate::game::player_color;
+
+/// Most players a game can have, one for each color
+pub const MAX_PLAYERS: usize = 13;
+
+/// Largest board side that still fits in a terminal
+const MAX_SIZE: isize = 40;
+
+/// Rows of the form above the player rows
+const BOARD_ROWS: usize = 5;
+
+/// The settings form shown before each game, with the same options as the app
+pub struct Settings {
+    pub width: isize,
+    pub height: isize,
+    pub goal: isize,
+    pub placement: Placement,
+    pub players: Vec<PlayerType>,
+    /// Seed for the computer players, or a random one each game when `None`
+    pub seed: Option<u64>,
+    /// Minimum time each computer move takes
+    pub delay: Duration,
+    pub weight_table: Arc<WeightTable>,
+    /// The row with the cursor: width, height, goal, placement and player
+    /// count, then one row for each player
+    row: usize,
+    /// The part of a player row being changed: its type, difficulty, strategy
+    /// or engine
+    part: usize,
+}
+
+/// What the form wants done after a key press
+pub enum SettingsAction {
+    None,
+    Start(Box<Game>),
+    Quit,
+}
+
+impl Settings {
+    pub fn new(
+        width: isize,
+        height: isize,
+        goal: isize,
+        placement: Placement,
+        players: Vec<PlayerType>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            goal,
+            placement,
+            players,
+            seed: None,
+            delay: Duration::ZERO,
+            weight_table: Arc::default(),
+            row: 0,
+            part: 0,
+        }
+    }
+
+    pub fn get_errors(&self) -> Vec<NewGameError> {
+        let mut errors = Game::validate(self.width, self.height, self.goal, self.players.len());
+        errors.extend(Game::validate_players(&self.players));
+        errors
+    }
+
+    pub fn to_game(&self) -> Result<Game, NewGameError> {
+        let mut game = Game::new(
+            self.width,
+            self.height,
+            self.goal,
+            self.players.clone(),
+            self.placement,
+        )?;
+        if let Some(seed) = self.seed {
+            game.set_seed(seed);
+        }
+        game.set_weight_table(self.weight_table.clone());
+        Ok(game)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SettingsAction {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return SettingsAction::Quit,
+            KeyCode::Enter => {
+                if let Ok(game) = self.to_game() {
+                    return SettingsAction::Start(Box::new(game));
+                }
+            }
+            KeyCode::Up => self.row = self.row.saturating_sub(1),
+            KeyCode::Down => self.row = (self.row + 1).min(BOARD_ROWS + self.players.len() - 1),
+            KeyCode::Tab => self.part += 1,
+            KeyCode::BackTab => self.part = self.part.saturating_sub(1),
+            KeyCode::Left => self.change(-1),
+            KeyCode::Right => self.change(1),
+            _ => {}
+        }
+        self.part = match self.get_player_row() {
+            Some(PlayerType::Computer(_) | PlayerType::External(_)) => self.part.min(3),
+            _ => 0,
+        };
+        SettingsAction::None
+    }
+
+    /// The player on the cursor row, if it is a player row
+    fn get_player_row(&self) -> Option<PlayerType> {
+        self.row
+            .checked_sub(BOARD_ROWS)
+            .and_then(|player| self.players.get(player).copied())
+    }
+
+    /// Steps the value under the cursor up or down
+    fn change(&mut self, step: isize) {
+        match self.row {
+            0 => self.width = (self.width + step).clamp(2, MAX_SIZE),
+            1 => self.height = (self.height + step).clamp(2, MAX_SIZE),
+            2 => self.goal = (self.goal + step).clamp(2, MAX_SIZE),
+            3 => self.placement = cycle(&Placement::ALL, self.placement, step),
+            4 => {
+                if step > 0 && self.players.len() < MAX_PLAYERS {
+                    self.players.push(PlayerType::Computer(Bot::default()));
+                } else if step < 0 && self.players.len() > 2 {
+                    self.players.pop();
+                }
+            }
+            row => {
+                let player = &mut self.players[row - BOARD_ROWS];
+                *player = match (*player, self.part) {
+                    (PlayerType::User | PlayerType::Remote, _) => {
+                        PlayerType::Computer(Bot::default())
+                    }
+                    (PlayerType::Computer(_) | PlayerType::External(_), 0) => PlayerType::User,
+                    (PlayerType::Computer(bot) | PlayerType::External(bot), 1) => {
+                        player.set_difficulty(cycle(&Difficulty::ALL, bot.get_difficulty(), step))
+                    }
+                    (PlayerType::Computer(bot) | PlayerType::External(bot), 2) => {
+                        player.set_strategy(cycle(&Strategy::ALL, bot.get_strategy(), step))
+                    }
+                    (PlayerType::Computer(bot) | PlayerType::External(bot), _) => {
+                        player.set_engine(cycle(&Engine::ALL, bot.get_engine(), step))
+                    }
+                };
+            }
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let selected = Style::new().add_modifier(Modifier::REVERSED);
+        let field = |row: usize, label: String, value: String| {
+            let style = if self.row == row {
+                selected
+            } else {
+                Style::new()
+            };
+            Line::from(vec![
+                Span::raw(format!("{label:<12}")),
+                Span::styled(format!(" {value} "), style),
+            ])
+        };
+
+        let mut lines = vec![
+            field(0, String::from("Width"), self.width.to_string()),
+            field(1, String::from("Height"), self.height.to_string()),
+            field(2, String::from("Goal"), self.goal.to_string()),
+            field(3, String::from("Placement"), self.placement.to_string()),
+            field(4, String::from("Players"), self.players.len().to_string()),
+            Line::default(),
+        ];
+        lines.extend(
+            self.players
+                .iter()
+                .enumerate()
+                .map(|(player, player_type)| {
+                    let row = BOARD_ROWS + player;
+                    let parts = match player_type {
+                        PlayerType::User => vec![String::from("User")],
+                        PlayerType::Remote => vec![String::from("Remote")],
+                        PlayerType::Computer(bot) | PlayerType::External(bot) => vec![
+                            String::from(match player_type {
+                                PlayerType::External(_) => "External",
+                                _ => "Computer",
+                            }),
+                            bot.get_difficulty().to_string(),
+                            bot.get_strategy().to_string(),
+                            bot.get_engine().to_string(),
+                        ],
+                    };
+                    let mut spans = vec![Span::styled(
+                        format!("{:<12}", format!("Player {}", player + 1)),
+                        Style::new().fg(player_color(player)),
+                    )];
+                    spans.extend(parts.into_iter().enumerate().map(|(part, value)| {
+                        let style = if self.row == row && self.part == part {
+                            selected
+                        } else {
+                            Style::new()
+                        };
+                        Span::styled(format!(" {value} "), style)
+                    }));
+                    Line::from(spans)
+                }),
+        );
+
+        lines.push(Line::default());
+        lines.extend(
+            self.get_errors()
+                .iter()
+                .map(|error| Line::from(error.to_string()).fg(Color::Red)),
+        );
+        lines.push(Line::from("↑↓ choose  ←→ change  Tab next part  Enter start  q quit").dim());
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Four in a Row ")),
+            area,
+        );
+    }
+}
+
+/// The value `step` places along from `value` in `all`, wrapping around
+fn cycle<T: Copy + PartialEq>(all: &[T], value: T, step: isize) -> T {
+    let index = all.iter().position(|&item| item == value).unwrap_or(0) as isize;
+    all[(index + step).rem_euclid(all.len() as isize) as usize]
+}